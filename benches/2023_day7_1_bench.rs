@@ -1,95 +1,112 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::measurement::WallTime;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+use rust_aoc::testutil::XorShift;
 // qualify directly for better readability
 use rust_aoc::y2023::day7_1;
 use rust_aoc::y2023::day7_1_slow_methods;
 use rust_aoc::y2023::day7_1_7bit_matrix;
 use rust_aoc::y2023::day7_1_matrix_13;
+use rust_aoc::y2023::day7_2;
+use rust_aoc::y2023::day7_1::Typ;
+use rust_aoc::y2023::day7_rules::RuleSet;
+
+/// Hands per corpus, every strategy classifies the same ones.
+const CORPUS_SIZE: usize = 10_000;
 
 pub fn bench_initialize_typ_matrix(c: &mut Criterion) {
     let mut group = c.benchmark_group("initialize_typ_matrix");
 
     group.bench_function("5^5 array", |b| b.iter(|| black_box(day7_1::initialize_typ_matrix())));
-    group.bench_function("5^13 array", |b| b.iter(|| black_box(day7_1::initialize_typ_matrix())));
+    //returning the whole 13^5 array from the closure crashes rustc's codegen, a single entry keeps the call alive
+    group.bench_function("13^5 array", |b| b.iter(|| black_box(day7_1_matrix_13::initialize_typ_matrix_13()[12][12][12][12][12])));
     group.bench_function("bit_shift", |b| b.iter(|| black_box(day7_1_7bit_matrix::initialize_typ_matrix_bit_shift())));
+    group.bench_function("6^5 joker array", |b| b.iter(|| black_box(day7_2::initialize_typ_matrix_joker())));
 
     group.finish();
 }
 
-/// tests the transformation of [usize;5] card array to Typ
+/// Uniformly random hands, like dealt from shuffled cards, the same on every run.
+fn random_hands(count: usize) -> Vec<[usize; 5]> {
+    let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+    let mut next_card = move || random.below(13) as usize;
+    (0..count)
+        .map(|_| [next_card(), next_card(), next_card(), next_card(), next_card()])
+        .collect()
+}
+
+/// Checks `classify` on the whole corpus first, a fast but wrong strategy isn't worth measuring,
+/// then measures classifying all of it.
+fn bench_strategy(group: &mut BenchmarkGroup<WallTime>, name: &str, corpus: &[([usize; 5], Typ)], classify: impl Fn([usize; 5]) -> Typ) {
+    for (cards, expected) in corpus {
+        assert_eq!(*expected, classify(*cards), "{} disagrees on {:?}", name, cards);
+    }
+    group.bench_function(name, |b| b.iter(|| {
+        for (cards, _) in corpus {
+            black_box(classify(black_box(*cards)));
+        }
+    }));
+}
+
+fn bench_reducer<T>(group: &mut BenchmarkGroup<WallTime>, name: &str, corpus: &[[usize; 5]], reduce: impl Fn([usize; 5]) -> T) {
+    group.bench_function(name, |b| b.iter(|| {
+        for cards in corpus {
+            black_box(reduce(black_box(*cards)));
+        }
+    }));
+}
+
+fn typ_matrix(reduced: [usize; 5]) -> Typ {
+    day7_1::TYP_MATRIX[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]]
+}
+
+fn typ_matrix_joker(reduced: [usize; 5]) -> Typ {
+    day7_2::TYP_MATRIX_JOKER[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]]
+}
+
+/// Every reducer with every classifier of reduced hands, and the strategies without a separate reduction,
+/// compared to the brute force of `RuleSet::check_exhaustively`.
 pub fn bench_identify_hand_type(c: &mut Criterion) {
     let mut group = c.benchmark_group("identify_hand_type");
+    let rules = RuleSet::camel_cards();
+    let corpus: Vec<([usize; 5], Typ)> = random_hands(CORPUS_SIZE).into_iter()
+        .map(|cards| (cards, rules.brute_force_typ(cards)))
+        .collect();
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+
+    //spelled out instead of looping over fn pointers, so every pair gets inlined like in the solution
+    bench_strategy(&mut group, "static + TYP_MATRIX", &corpus, |cards| typ_matrix(day7_1::reduce_variant_range(cards)));
+    bench_strategy(&mut group, "static + identify_hand_type", &corpus, |cards| day7_1::identify_hand_type(day7_1::reduce_variant_range(cards)));
+    bench_strategy(&mut group, "half static + TYP_MATRIX", &corpus, |cards| typ_matrix(day7_1_slow_methods::reduce_variant_range_half_static(cards)));
+    bench_strategy(&mut group, "half static + identify_hand_type", &corpus, |cards| day7_1::identify_hand_type(day7_1_slow_methods::reduce_variant_range_half_static(cards)));
+    bench_strategy(&mut group, "slow + TYP_MATRIX", &corpus, |cards| typ_matrix(day7_1_slow_methods::reduce_variant_range_slow(cards)));
+    bench_strategy(&mut group, "slow + identify_hand_type", &corpus, |cards| day7_1::identify_hand_type(day7_1_slow_methods::reduce_variant_range_slow(cards)));
+    bench_strategy(&mut group, "RuleSet + TYP_MATRIX", &corpus, |cards| typ_matrix(rules.reduce_variant_range(cards)));
+    bench_strategy(&mut group, "RuleSet + identify_hand_type", &corpus, |cards| day7_1::identify_hand_type(rules.reduce_variant_range(cards)));
+    bench_strategy(&mut group, "bit shift + TYP_MATRIX_BIT_SHIFT", &corpus, |cards| {
+        day7_1_7bit_matrix::TYP_MATRIX_BIT_SHIFT[day7_1_7bit_matrix::reduce_variant_range_bit_shift(cards)]
+    });
+    bench_strategy(&mut group, "TYP_MATRIX 13^5", &corpus, |cards| {
+        day7_1_matrix_13::TYP_MATRIX[cards[0]][cards[1]][cards[2]][cards[3]][cards[4]]
+    });
+    bench_strategy(&mut group, "identify_hand_type13", &corpus, day7_1_slow_methods::identify_hand_type13);
+    bench_strategy(&mut group, "RuleSet::typ", &corpus, |cards| rules.typ(cards));
 
-    let inputs: [[usize; 5]; 2] = [
-        [0, 1, 2, 3, 4],
-        [0, 3, 9, 5, 12]
-    ];
-    for (idx, input) in inputs.into_iter().enumerate() {
-        // group.bench_with_input(BenchmarkId::new("identify_hand_type reduce_variant_range_slow", idx), 
-        //     &input, |b, input| b.iter(|| {
-        //         let cards_reduced_range = day7_1_slow_methods::reduce_variant_range_slow(*input);
-        //         let typ = day7_1::identify_hand_type(cards_reduced_range);
-        //         black_box(typ);
-        //     })
-        // );
-        // group.bench_with_input(BenchmarkId::new("identify_hand_type reduce_variant_range", idx), 
-        //     &input, |b, input| b.iter(|| {
-        //         let cards_reduced_range = day7_1::reduce_variant_range(*input);
-        //         let typ = day7_1::identify_hand_type(cards_reduced_range);
-        //         black_box(typ);
-        //     })
-        // );
-        // group.bench_with_input(BenchmarkId::new("TYP_MATRIX reduce_variant_range_slow", idx), 
-        //     &input, |b, input| b.iter(|| {
-        //         let cards_reduced_range = day7_1_slow_methods::reduce_variant_range_slow(*input);
-        //         let typ = day7_1::TYP_MATRIX[cards_reduced_range[0]]
-        //             [cards_reduced_range[1]]
-        //             [cards_reduced_range[2]]
-        //             [cards_reduced_range[3]]
-        //             [cards_reduced_range[4]];
-        //         black_box(typ);
-        //     })
-        // );
-        group.bench_with_input(BenchmarkId::new("TYP_MATRIX (bit_shift)", idx), 
-            &input, |b, input| b.iter(|| {
-                let cards_reduced_range = day7_1_7bit_matrix::reduce_variant_range_bit_shift(*input);
-                let typ = day7_1_7bit_matrix::TYP_MATRIX_BIT_SHIFT[cards_reduced_range];
-                black_box(typ);
-            })
-        );
-        group.bench_with_input(BenchmarkId::new("TYP_MATRIX (static)", idx), 
-            &input, |b, input| b.iter(|| {
-                let cards_reduced_range = day7_1::reduce_variant_range(*input);
-                let typ = day7_1::TYP_MATRIX[cards_reduced_range[0]]
-                    [cards_reduced_range[1]]
-                    [cards_reduced_range[2]]
-                    [cards_reduced_range[3]]
-                    [cards_reduced_range[4]];
-                black_box(typ);
-            })
-        );
-        group.bench_with_input(BenchmarkId::new("TYP_MATRIX 13^5", idx), 
-            &input, |b, input| b.iter(|| {
-                let typ = day7_1::TYP_MATRIX[input[0]]
-                    [input[1]]
-                    [input[2]]
-                    [input[3]]
-                    [input[4]];
-                black_box(typ);
-            })
-        );
-        group.bench_with_input(BenchmarkId::new("identify_hand_type13", idx), 
-            &input, |b, input| b.iter(|| {
-                let typ = day7_1_slow_methods::identify_hand_type13(*input);
-                black_box(typ);
-            })
-        );
-        group.bench_with_input(BenchmarkId::new("identify_hand_type13", idx), 
-            &input, |b, input| b.iter(|| {
-                let typ = day7_1_slow_methods::identify_hand_type13(*input);
-                black_box(typ);
-            })
-        );
-    }
+    group.finish();
+}
+
+/// Part 2, the joker reduction with every joker classifier.
+pub fn bench_identify_joker_hand_type(c: &mut Criterion) {
+    let mut group = c.benchmark_group("identify_joker_hand_type");
+    let rules = RuleSet::jokers();
+    let corpus: Vec<([usize; 5], Typ)> = random_hands(CORPUS_SIZE).into_iter()
+        .map(|cards| (cards, rules.brute_force_typ(cards)))
+        .collect();
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+
+    bench_strategy(&mut group, "RuleSet + TYP_MATRIX_JOKER", &corpus, |cards| typ_matrix_joker(rules.reduce_variant_range(cards)));
+    bench_strategy(&mut group, "RuleSet + identify_joker_hand_type", &corpus, |cards| day7_2::identify_joker_hand_type(rules.reduce_variant_range(cards)));
+    bench_strategy(&mut group, "RuleSet + identify_hand_type (dynamic)", &corpus, |cards| day7_2::identify_hand_type(rules.reduce_variant_range(cards)));
+    bench_strategy(&mut group, "RuleSet::typ", &corpus, |cards| rules.typ(cards));
 
     group.finish();
 }
@@ -132,48 +149,21 @@ bit_shift + matrix with len 152:
 
 "
 */
+/// The reducers on their own, their results differ so there's nothing to compare.
 pub fn bench_reduce_variant_range(c: &mut Criterion) {
     let mut group = c.benchmark_group("reduce_variant_range");
-// 2024/04/28 10:12: theoreticallty unstabler benches due to low power mode
-    let inputs: [[usize; 5]; 7] = [
-        //using the input values that require theoretically more range transformation
-        [12, 6, 3, 2, 1], //HighCard
-        [2, 6, 3, 12, 2],//Pair
-        [12, 6, 3, 12, 3],//TwoPair
-        [12, 6, 3, 3, 3],//ThreeOfAKind
-        [12, 6, 6, 6, 12],//FullHouse
-        [11, 12, 12, 12, 12],//FourOfAKind
-        [12, 12, 12, 12, 12],//FiveOfAKind
-    ];
-    for (idx, input) in inputs.into_iter().enumerate() {
-        // group.bench_with_input(BenchmarkId::new("half static", idx), 
-        //     &input, |b, input| b.iter(|| {
-        //         let cards_reduced_range = day7_1_slow_methods::reduce_variant_range_half_static(*input);
-        //         black_box(cards_reduced_range);
-        //     })
-        // );
-        group.bench_with_input(BenchmarkId::new("bit shift", idx), 
-            &input, |b, input| b.iter(|| {
-                let cards_reduced_range = day7_1_7bit_matrix::reduce_variant_range_bit_shift(*input);
-                black_box(cards_reduced_range);
-            })
-        );
-        group.bench_with_input(BenchmarkId::new("static", idx), 
-            &input, |b, input| b.iter(|| {
-                let cards_reduced_range = day7_1::reduce_variant_range(*input); //full static
-                black_box(cards_reduced_range);
-            })
-        );
-        // group.bench_with_input(BenchmarkId::new("reduce_variant_range_slow", idx), 
-        //     &input, |b, input| b.iter(|| {
-        //         let cards_reduced_range = day7_1_slow_methods::reduce_variant_range_slow(*input);
-        //         black_box(cards_reduced_range);
-        //     })
-        // );
-    }
+    let corpus = random_hands(CORPUS_SIZE);
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+
+    let rules = RuleSet::camel_cards();
+    bench_reducer(&mut group, "static", &corpus, day7_1::reduce_variant_range);
+    bench_reducer(&mut group, "half static", &corpus, day7_1_slow_methods::reduce_variant_range_half_static);
+    bench_reducer(&mut group, "slow", &corpus, day7_1_slow_methods::reduce_variant_range_slow);
+    bench_reducer(&mut group, "bit shift", &corpus, day7_1_7bit_matrix::reduce_variant_range_bit_shift);
+    bench_reducer(&mut group, "RuleSet", &corpus, |cards| rules.reduce_variant_range(cards));
 
     group.finish();
 }
 
-criterion_group!(benches, bench_initialize_typ_matrix, bench_identify_hand_type);
+criterion_group!(benches, bench_initialize_typ_matrix, bench_identify_hand_type, bench_identify_joker_hand_type, bench_reduce_variant_range);
 criterion_main!(benches);
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{parse_number, ParseError};
+use crate::solution::Answer;
+
+/// Known answers of one year, e.g. the accepted ones in `<root>/y<year>/answers.toml`:
+///
+/// ```toml
+/// [day7]
+/// part1 = 6440
+/// part2 = 5905
+/// ```
+///
+/// Only this subset of TOML is understood: `[dayN]` tables holding `partN` keys
+/// with an integer or a double quoted string, plus comments and blank lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, ParseError> {
+        let mut answers = HashMap::new();
+        let mut day: Option<u8> = None;
+        for (idx, line) in text.lines().enumerate() {
+            parse_line(line, &mut day, &mut answers).map_err(|e| e.in_line(idx + 1))?;
+        }
+        return Ok(Answers { answers: answers });
+    }
+
+    /// A missing file means no known answers yet, that's no error.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(AnswersError::Read(path.to_path_buf(), e)),
+        };
+        Answers::parse(&text).map_err(|e| AnswersError::Parse(path.to_path_buf(), e))
+    }
+}
+
+/// `<root>/y<year>/answers.toml`, the accepted answers for the inputs next to it.
+pub fn answers_path(root: &Path, year: u16) -> PathBuf {
+    root.join(format!("y{}", year)).join("answers.toml")
+}
+
+fn parse_line(line: &str, day: &mut Option<u8>, answers: &mut HashMap<(u8, u8), Answer>) -> Result<(), ParseError> {
+    let content = strip_comment(line).trim();
+    if content.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(table) = content.strip_prefix('[') {
+        let name = table.strip_suffix(']')
+            .ok_or_else(|| ParseError::at("Missing ']' after table name", line, table))?
+            .trim();
+        let number = name.strip_prefix("day")
+            .ok_or_else(|| ParseError::at(format!("Expected a table [dayN], got [{}]", name), line, name))?;
+        *day = Some(parse_number(line, number)?);
+        return Ok(());
+    }
+
+    let (key, value) = content.split_once('=')
+        .ok_or_else(|| ParseError::at("Expected 'partN = answer'", line, content))?;
+    let (key, value) = (key.trim(), value.trim());
+    let day = day.ok_or_else(|| ParseError::at("Answer outside of a [dayN] table", line, key))?;
+    let part = key.strip_prefix("part")
+        .ok_or_else(|| ParseError::at(format!("Expected a key partN, got '{}'", key), line, key))?;
+    let part: u8 = parse_number(line, part)?;
+    let answer = parse_value(line, value)?;
+    if answers.insert((day, part), answer).is_some() {
+        return Err(ParseError::at(format!("Answer for day {} part {} given twice", day, part), line, key));
+    }
+    return Ok(());
+}
+
+fn parse_value(line: &str, value: &str) -> Result<Answer, ParseError> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        let text = quoted.strip_suffix('"')
+            .ok_or_else(|| ParseError::at("Missing closing '\"'", line, value))?;
+        return Ok(Answer::Text(text.to_string()));
+    }
+    return Ok(Answer::Number(parse_number(line, value)?));
+}
+
+/// Everything before a `#` that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {}
+        }
+    }
+    return line;
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, ParseError),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Read(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            AnswersError::Parse(path, e) => write!(f, "invalid answers file {}, {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Read(_, e) => Some(e),
+            AnswersError::Parse(_, e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::temp_dir_with;
+
+    #[test]
+    fn test_parse() {
+        let text = "# accepted answers\n\
+                    [day1]\n\
+                    part1 = 142\n\
+                    \n\
+                    [day7]   # camel cards\n\
+                    part2 = -5905\n\
+                    part1 = \"a#b\"\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(3, answers.len());
+        assert_eq!(Some(&Answer::Number(142)), answers.get(1, 1));
+        assert_eq!(None, answers.get(1, 2));
+        assert_eq!(Some(&Answer::Number(-5905)), answers.get(7, 2));
+        assert_eq!(Some(&Answer::Text("a#b".to_string())), answers.get(7, 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Answers::parse("part1 = 3").unwrap_err();
+        assert_eq!("line 1, column 1: Answer outside of a [dayN] table", error.to_string());
+        let error = Answers::parse("[day1]\npart1 = 3\npart1 = 4").unwrap_err();
+        assert_eq!(Some(3), error.line);
+        let error = Answers::parse("[day1]\npart1 = x3").unwrap_err();
+        assert_eq!("line 2, column 9: 'x3' is not a valid number", error.to_string());
+        assert!(Answers::parse("[dag1]").is_err());
+        assert!(Answers::parse("[day1\n").is_err());
+        assert!(Answers::parse("[day1]\npart1 = \"abc").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let root = temp_dir_with("answers", &[("y2023/answers.toml", "[day7]\npart1 = 6440")]);
+        assert_eq!(Some(&Answer::Number(6440)), Answers::load(&answers_path(&root, 2023)).unwrap().get(7, 1));
+        assert!(Answers::load(&answers_path(&root, 1999)).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::solution::Solution;
+
+pub const USAGE: &str = "usage: rust_aoc [verify] [YEAR [DAY [PART]]] [--year YEAR] [--day DAY] [--part PART]
+                [--input-dir DIR] [--input FILE] [--json] [--timeout SECONDS] [--variant NAME]
+
+Runs every registered solution matching the selection and prints its answer,
+parse and solve time and peak memory, e.g.
+    rust_aoc              all solved days
+    rust_aoc 2023         every day of 2023
+    rust_aoc 2023 7 2     2023 day 7 part 2
+    rust_aoc --day 7      day 7 of every year
+    rust_aoc verify 2023  compare every day of 2023 with its accepted answers
+
+Inputs are read from DIR/yYYYY/dayD.txt (or dayD_P.txt if a part needs its own),
+DIR defaults to $AOC_INPUT_DIR or the crate's res directory.
+--input reads FILE for every selected part instead, '-' reads stdin.
+--json prints the measurements as JSON instead of a table.
+--timeout cancels a solution after SECONDS, Ctrl-C cancels the running one and skips the rest.
+Only solutions reporting their progress (the brute forces) notice, they stop with how far they got.
+--variant runs the solutions named NAME instead, e.g. the brute-force one of 2023 day 5 part 2.
+verify reads the accepted answers from DIR/yYYYY/answers.toml, parts without one are 'unknown'.
+Inputs and answers aren't part of the repository, put your own into DIR.";
+
+/// What to do with the selected solutions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Print the answers.
+    #[default]
+    Run,
+    /// Compare the answers with the accepted ones.
+    Verify,
+}
+
+/// Which solutions to run, unset fields match everything.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    pub mode: Mode,
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input_dir: Option<PathBuf>,
+    pub input: Option<PathBuf>,
+    pub json: bool,
+    /// Per solution run.
+    pub timeout: Option<Duration>,
+    /// Selects `Solution::variant`s instead of the regular solutions.
+    pub variant: Option<String>,
+    pub help: bool,
+}
+
+impl Args {
+    /// Parses the arguments without the program name.
+    /// A leading `verify` selects the mode, positional numbers fill year, day and part in that order.
+    pub fn parse(args: impl IntoIterator<Item=String>) -> Result<Args, String> {
+        let mut result = Args::default();
+        let mut positional: Vec<String> = vec![];
+        let mut args = args.into_iter().peekable();
+
+        if args.peek().is_some_and(|arg| arg == "verify") {
+            result.mode = Mode::Verify;
+            args.next();
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => result.help = true,
+                "-y" | "--year" => result.year = Some(parse_number(&arg, args.next())?),
+                "-d" | "--day" => result.day = Some(parse_number(&arg, args.next())?),
+                "-p" | "--part" => result.part = Some(parse_number(&arg, args.next())?),
+                "--input-dir" => result.input_dir = Some(parse_path(&arg, args.next())?),
+                "-i" | "--input" => result.input = Some(parse_path(&arg, args.next())?),
+                "--json" => result.json = true,
+                "--timeout" => result.timeout = Some(parse_duration(&arg, args.next())?),
+                "--variant" => result.variant = Some(args.next().ok_or_else(|| format!("'{}' requires a value", arg))?),
+                //a lone '-' is stdin for --input, not an option
+                "-" => return Err("'-' is only valid as value of --input".to_string()),
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        if result.json && result.mode == Mode::Verify {
+            return Err("--json is not supported by verify".to_string());
+        }
+        if positional.len() > 3 {
+            return Err(format!("Expected at most YEAR DAY PART, got '{}'", positional.join(" ")));
+        }
+        let mut positional = positional.into_iter();
+        if let Some(year) = positional.next() {
+            set_once(&mut result.year, parse_number("YEAR", Some(year))?, "year")?;
+        }
+        if let Some(day) = positional.next() {
+            set_once(&mut result.day, parse_number("DAY", Some(day))?, "day")?;
+        }
+        if let Some(part) = positional.next() {
+            set_once(&mut result.part, parse_number("PART", Some(part))?, "part")?;
+        }
+
+        Ok(result)
+    }
+
+    /// What was asked for, e.g. `2023 day 8` or `day 5 part 2 (brute-force)`.
+    pub fn selection(&self) -> String {
+        let mut selection: Vec<String> = vec![];
+        if let Some(year) = self.year {
+            selection.push(year.to_string());
+        }
+        if let Some(day) = self.day {
+            selection.push(format!("day {}", day));
+        }
+        if let Some(part) = self.part {
+            selection.push(format!("part {}", part));
+        }
+        if selection.is_empty() {
+            selection.push("any day".to_string());
+        }
+        if let Some(variant) = &self.variant {
+            selection.push(format!("({})", variant));
+        }
+        return selection.join(" ");
+    }
+
+    pub fn matches(&self, solution: &dyn Solution) -> bool {
+        self.year.is_none_or(|year| year == solution.year())
+            && self.day.is_none_or(|day| day == solution.day())
+            && self.part.is_none_or(|part| part == solution.part())
+            && self.variant.as_deref() == solution.variant()
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("'{}' requires a value", name))?;
+    value.parse::<T>()
+        .map_err(|_| format!("'{}' is not a valid number for {}", value, name))
+}
+
+/// Seconds, fractions allowed.
+fn parse_duration(name: &str, value: Option<String>) -> Result<Duration, String> {
+    let seconds: f64 = parse_number(name, value)?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("'{}' is not a valid number of seconds for {}", seconds, name))
+}
+
+fn parse_path(name: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value.map(PathBuf::from)
+        .ok_or_else(|| format!("'{}' requires a value", name))
+}
+
+fn set_once<T>(field: &mut Option<T>, value: T, name: &str) -> Result<(), String> {
+    if field.is_some() {
+        return Err(format!("{} given twice", name));
+    }
+    *field = Some(value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        Args::parse(args.split_whitespace().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_positional() {
+        assert_eq!(Args::default(), parse("").unwrap());
+        assert_eq!(Args { year: Some(2023), ..Args::default() }, parse("2023").unwrap());
+        assert_eq!(Args { year: Some(2023), day: Some(7), part: Some(2), ..Args::default() }, parse("2023 7 2").unwrap());
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(Args { year: Some(2023), day: Some(7), part: Some(2), ..Args::default() },
+            parse("--year 2023 -d 7 --part 2").unwrap());
+        assert_eq!(Args { year: Some(2024), day: Some(1), ..Args::default() }, parse("2024 --day 1").unwrap());
+        assert!(parse("-h").unwrap().help);
+        assert!(parse("2023 --json").unwrap().json);
+        assert_eq!(Args { input_dir: Some(PathBuf::from("inputs")), input: Some(PathBuf::from("-")), ..Args::default() },
+            parse("--input-dir inputs -i -").unwrap());
+        assert_eq!(Some(Duration::from_millis(2500)), parse("--timeout 2.5").unwrap().timeout);
+        assert_eq!(Some("brute-force".to_string()), parse("--variant brute-force").unwrap().variant);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(Mode::Run, parse("2023 7").unwrap().mode);
+        assert_eq!(Args { mode: Mode::Verify, year: Some(2023), ..Args::default() }, parse("verify 2023").unwrap());
+        assert_eq!(Args { mode: Mode::Verify, ..Args::default() }, parse("verify").unwrap());
+        //only as first argument
+        assert!(parse("2023 verify").is_err());
+        assert!(parse("verify --json").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("--day").is_err());
+        assert!(parse("--day seven").is_err());
+        assert!(parse("--verbose").is_err());
+        assert!(parse("2023 --year 2024").is_err());
+        assert!(parse("2023 7 2 1").is_err());
+        assert!(parse("--input").is_err());
+        assert!(parse("--timeout -1").is_err());
+        assert!(parse("--timeout soon").is_err());
+        assert!(parse("--variant").is_err());
+    }
+
+    #[test]
+    fn test_selection() {
+        assert_eq!("2023 day 8", parse("2023 8").unwrap().selection());
+        assert_eq!("day 5 part 2 (brute-force)", parse("--day 5 --part 2 --variant brute-force").unwrap().selection());
+        assert_eq!("any day", parse("--json").unwrap().selection());
+    }
+
+    #[test]
+    fn test_matches() {
+        let args = parse("2023 7").unwrap();
+        let selected: Vec<(u16, u8, u8)> = solution::all().into_iter()
+            .filter(|solution| args.matches(*solution))
+            .map(|solution| (solution.year(), solution.day(), solution.part()))
+            .collect();
+        assert_eq!(vec![(2023, 7, 1), (2023, 7, 2)], selected);
+
+        //one solution per part, unless a variant is asked for
+        let selected = |args: &str| -> Vec<(u8, Option<&str>)> {
+            let args = parse(args).unwrap();
+            solution::all().into_iter()
+                .filter(|solution| args.matches(*solution))
+                .map(|solution| (solution.part(), solution.variant()))
+                .collect()
+        };
+        assert_eq!(vec![(1, None), (2, None)], selected("2023 5"));
+        assert_eq!(vec![(2, Some("brute-force"))], selected("2023 5 --variant brute-force"));
+        assert!(selected("2023 7 --variant brute-force").is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::progress::Cancelled;
+
+/// Why a solver could not produce an answer for its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input doesn't have the format of the puzzle.
+    Parse(ParseError),
+    /// The input is well formed, but has no answer (e.g. no winning hand, no location).
+    NoSolution(String),
+    /// Stopped before finding the answer, by a timeout or Ctrl-C.
+    Cancelled(Cancelled),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "invalid input, {}", e),
+            Error::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            Error::Cancelled(cancelled) => write!(f, "cancelled, {}", cancelled),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(e) => Some(e),
+            Error::NoSolution(_) => None,
+            Error::Cancelled(cancelled) => Some(cancelled),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<Cancelled> for Error {
+    fn from(cancelled: Cancelled) -> Self {
+        Error::Cancelled(cancelled)
+    }
+}
+
+/// Malformed input, with the 1-based line and column of the problem where known.
+///
+/// Parsers of a single line only know the column,
+/// the caller iterating the lines adds the line via `in_line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ParseError { line: None, column: None, message: message.into() }
+    }
+
+    /// Points at `part`, which has to be a slice of `line`.
+    pub fn at(message: impl Into<String>, line: &str, part: &str) -> Self {
+        ParseError { line: None, column: column_of(line, part), message: message.into() }
+    }
+
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Moves the column of an error found in `part` to its position in `line`,
+    /// for parsers that only got handed a slice of the line.
+    pub fn within(mut self, line: &str, part: &str) -> Self {
+        if let (Some(column), Some(offset)) = (self.column, column_of(line, part)) {
+            self.column = Some(column + offset - 1);
+        }
+        self
+    }
+
+    /// Sets the 1-based line, unless a nested parser already knew better.
+    pub fn in_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {}, column {}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "line {}: {}", line, self.message),
+            (None, Some(column)) => write!(f, "column {}: {}", column, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `part` of `line`, pointing at `part` if it is no valid number.
+pub fn parse_number<T: FromStr>(line: &str, part: &str) -> Result<T, ParseError> {
+    part.parse::<T>()
+        .map_err(|_| ParseError::at(format!("'{}' is not a valid number", part), line, part))
+}
+
+/// Parses each line of `text`, errors get the number of the failing line.
+pub fn parse_lines<T, F>(text: &str, parse: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&str) -> Result<T, ParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(idx, line)| parse(line).map_err(|e| e.in_line(idx + 1)))
+        .collect()
+}
+
+/// 1-based column of the slice `part` in `line`, None if `part` doesn't point into `line`.
+fn column_of(line: &str, part: &str) -> Option<usize> {
+    let offset = (part.as_ptr() as usize).checked_sub(line.as_ptr() as usize)?;
+    if offset > line.len() {
+        return None;
+    }
+    return Some(offset + 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        let line = "Card 12: 41 x8 83";
+        assert_eq!(Ok(41), parse_number::<u32>(line, &line[9..11]));
+        assert_eq!(Err(ParseError { line: None, column: Some(13), message: "'x8' is not a valid number".to_string() }),
+            parse_number::<u32>(line, &line[12..14]));
+        //not a slice of the line, no column
+        assert_eq!(None, parse_number::<u32>(line, "x").unwrap_err().column);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let text = "1 2\n\
+                    3 4\n\
+                    5 x";
+        let parse = |line: &str| line.split_whitespace()
+            .map(|nr| parse_number::<u32>(line, nr))
+            .collect::<Result<Vec<u32>, ParseError>>();
+        assert_eq!(Ok(vec![vec![1, 2], vec![3, 4]]), parse_lines("1 2\n3 4", parse));
+        let error = parse_lines(text, parse).unwrap_err();
+        assert_eq!((Some(3), Some(3)), (error.line, error.column));
+        assert_eq!("line 3, column 3: 'x' is not a valid number", error.to_string());
+    }
+
+    #[test]
+    fn test_within() {
+        let line = "Game 1: 3 blue, x red";
+        let set = &line[16..];
+        let error = parse_number::<u32>(set, &set[0..1]).unwrap_err();
+        assert_eq!(Some(1), error.column);
+        assert_eq!(Some(17), error.within(line, set).column);
+    }
+
+    #[test]
+    fn test_in_line_keeps_inner_line() {
+        let error = ParseError::new("map without name").in_line(7).in_line(2);
+        assert_eq!(Some(7), error.line);
+        assert_eq!("invalid input, line 7: map without name", Error::from(error).to_string());
+    }
+}
@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use crate::answers::Answers;
+use crate::input::{find_day_file, read, InputError, DEFAULT_INPUT_DIR};
+use crate::solution::{Answer, Solution};
+
+/// The puzzle's example lives next to the input as `day<day>.example.txt`,
+/// or `day<day>_<part>.example.txt` if a part has its own example (2023 day 1).
+pub const EXAMPLE_SUFFIX: &str = ".example.txt";
+
+pub fn find_example(root: &Path, year: u16, day: u8, part: u8) -> PathBuf {
+    find_day_file(root, year, day, part, EXAMPLE_SUFFIX)
+}
+
+/// `<root>/y<year>/examples.toml`, the expected answers of the examples,
+/// same format as `answers.toml`.
+pub fn examples_path(root: &Path, year: u16) -> PathBuf {
+    root.join(format!("y{}", year)).join("examples.toml")
+}
+
+/// The example of a part from the crate's `res` directory, for tests.
+pub fn load(year: u16, day: u8, part: u8) -> Result<String, InputError> {
+    read(&find_example(Path::new(DEFAULT_INPUT_DIR), year, day, part))
+}
+
+/// Runs `solution` on its example, the error tells what is missing or which answer was wrong.
+///
+/// Every registered solution gets a test calling this, see `solution::register!`.
+pub fn check(solution: &dyn Solution) -> Result<Answer, String> {
+    let root = Path::new(DEFAULT_INPUT_DIR);
+    let (year, day, part) = (solution.year(), solution.day(), solution.part());
+    let label = format!("{} day {} part {}", year, day, part);
+
+    let input = load(year, day, part).map_err(|e| format!("{}: {}", label, e))?;
+    let path = examples_path(root, year);
+    let answers = Answers::load(&path).map_err(|e| e.to_string())?;
+    let expected = answers.get(day, part)
+        .ok_or_else(|| format!("{}: no expected answer in {}", label, path.display()))?;
+
+    let actual = solution.solve(&input).map_err(|e| format!("{}: {}", label, e))?;
+    if &actual != expected {
+        return Err(format!("{}: expected {} for the example, got {}", label, expected, actual));
+    }
+    return Ok(actual);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    struct Unsolved;
+
+    impl Solution for Unsolved {
+        fn year(&self) -> u16 { 2023 }
+        fn day(&self) -> u8 { 25 }
+        fn part(&self) -> u8 { 1 }
+        fn solve(&self, _input: &str) -> Result<Answer, Error> {
+            Ok(Answer::Number(0))
+        }
+    }
+
+    #[test]
+    fn test_find_example() {
+        let root = PathBuf::from(DEFAULT_INPUT_DIR);
+        assert_eq!(root.join("y2023/day1_2.example.txt"), find_example(&root, 2023, 1, 2));
+        assert_eq!(root.join("y2023/day7.example.txt"), find_example(&root, 2023, 7, 2));
+    }
+
+    #[test]
+    fn test_check_without_example() {
+        let error = check(&Unsolved).unwrap_err();
+        assert!(error.starts_with("2023 day 25 part 1: input file"), "{}", error);
+        assert!(error.contains("day25.example.txt"), "{}", error);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A directed graph, e.g. the almanac's categories with an edge for every `from-to-to map`.
+///
+/// Nodes and edges keep the order they were added in, so searches are deterministic.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    ids: HashMap<N, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph { nodes: vec![], ids: HashMap::new(), edges: vec![] }
+    }
+}
+
+impl<N: Eq + Hash + Clone> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.ids.contains_key(node)
+    }
+
+    /// Adds the node if it's new, returns its id either way.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.edges.push(vec![]);
+        return id;
+    }
+
+    /// Adds both nodes if they're new, an edge already there isn't added twice.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+        }
+    }
+
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.ids.get(node)
+            .into_iter()
+            .flat_map(|id| self.edges[*id].iter())
+            .map(|id| &self.nodes[*id])
+    }
+
+    /// The path with the fewest edges, both ends included, found breadth first.
+    /// None if either node is missing or `to` can't be reached.
+    pub fn shortest_path(&self, from: &N, to: &N) -> Option<Vec<&N>> {
+        let from = *self.ids.get(from)?;
+        let to = *self.ids.get(to)?;
+
+        //the node each node was first reached from
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        visited[from] = true;
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![&self.nodes[to]];
+                let mut current = to;
+                while let Some(before) = previous[current] {
+                    path.push(&self.nodes[before]);
+                    current = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in &self.edges[node] {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    previous[*next] = Some(node);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        return None;
+    }
+
+    /// Some cycle of the graph, starting and ending with the same node, None if there is none.
+    pub fn find_cycle(&self) -> Option<Vec<&N>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+
+        let mut states = vec![State::New; self.nodes.len()];
+        for start in 0..self.nodes.len() {
+            if states[start] != State::New {
+                continue;
+            }
+            //depth first, each entry is a node on the current path and the index of its next edge
+            let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+            states[start] = State::OnPath;
+            while let Some((node, edge)) = path.last_mut() {
+                let node = *node;
+                let Some(next) = self.edges[node].get(*edge).copied() else {
+                    states[node] = State::Done;
+                    path.pop();
+                    continue;
+                };
+                *edge += 1;
+                match states[next] {
+                    State::New => {
+                        states[next] = State::OnPath;
+                        path.push((next, 0));
+                    }
+                    State::OnPath => {
+                        let cycle_start = path.iter().position(|(id, _)| *id == next).expect("node is on the path");
+                        let mut cycle: Vec<&N> = path[cycle_start..].iter().map(|(id, _)| &self.nodes[*id]).collect();
+                        cycle.push(&self.nodes[next]);
+                        return Some(cycle);
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(*from, *to);
+        }
+        return graph;
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let graph = graph(&[("seed", "soil"), ("soil", "water"), ("water", "location"), ("seed", "water"), ("soil", "light")]);
+        assert_eq!(5, graph.len());
+        assert_eq!(vec![&"soil", &"water"], graph.successors(&"seed").collect::<Vec<_>>());
+
+        assert_eq!(Some(vec![&"seed", &"water", &"location"]), graph.shortest_path(&"seed", &"location"));
+        assert_eq!(Some(vec![&"soil", &"light"]), graph.shortest_path(&"soil", &"light"));
+        assert_eq!(Some(vec![&"seed"]), graph.shortest_path(&"seed", &"seed"));
+        assert_eq!(None, graph.shortest_path(&"location", &"seed"));
+        assert_eq!(None, graph.shortest_path(&"seed", &"humidity"));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph = graph(&[("seed", "soil"), ("soil", "water"), ("seed", "water"), ("water", "location")]);
+        assert_eq!(None, graph.find_cycle());
+
+        graph.add_edge("location", "soil");
+        assert_eq!(Some(vec![&"soil", &"water", &"location", &"soil"]), graph.find_cycle());
+        //paths only visit every node once, cycle or not
+        assert_eq!(Some(vec![&"location", &"soil"]), graph.shortest_path(&"location", &"soil"));
+
+        assert_eq!(Some(vec![&"a", &"a"]), self::graph(&[("a", "a")]).find_cycle());
+    }
+}
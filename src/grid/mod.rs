@@ -0,0 +1,392 @@
+use std::cmp;
+use std::fmt;
+use std::iter;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+mod region;
+
+pub use region::{Connectivity, Region, Regions};
+
+/// Position in a grid, `y` is the line and `x` the column, both 0 based.
+/// Points are ordered line by line, like the text they were parsed from.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+pub struct Point {
+    pub y: usize,
+    pub x: usize
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "y:{} x:{}", self.y, self.x)
+    }
+}
+
+impl Point {
+    pub fn new(y: usize, x: usize) -> Self {
+        Point {y: y, x: x}
+    }
+
+    /// The next point in `direction`, None if that would be left of or above 0.
+    pub fn step(&self, direction: Direction) -> Option<Point> {
+        let (dy, dx) = direction.offset();
+        let y = self.y.checked_add_signed(dy)?;
+        let x = self.x.checked_add_signed(dx)?;
+        Some(Point::new(y, x))
+    }
+
+    /// Top left and bottom right corner of the box around `length` cells starting at this point.
+    /// The box is cut off at 0, but may reach past the far edges of a grid.
+    pub fn surrounding_range(&self, length: usize) -> (Point, Point) {
+        (
+            (Point::new(
+                cmp::max(self.y, 1) - 1,
+                cmp::max(self.x, 1) - 1)),
+            (Point::new(self.y + 1, self.x + length)) //0 based!
+        )
+    }
+
+    pub fn parse_error(&self, message: impl Into<String>) -> ParseError {
+        //0 based, the error 1 based
+        ParseError::new(message).in_line(self.y + 1).with_column(self.x + 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The 4 directions sharing an edge, in reading order.
+    pub const STRAIGHT: [Direction; 4] = [Direction::Up, Direction::Left, Direction::Right, Direction::Down];
+    /// All 8 directions, in reading order.
+    pub const ALL: [Direction; 8] = [
+        Direction::UpLeft, Direction::Up, Direction::UpRight,
+        Direction::Left, Direction::Right,
+        Direction::DownLeft, Direction::Down, Direction::DownRight,
+    ];
+
+    /// (dy, dx) of one step.
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// Dense rectangular grid, stored row by row.
+///
+/// Every access is bounds-checked and returns None outside of the grid,
+/// the iterators only ever yield points inside.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// All rows need the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, ParseError> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ParseError::new(format!("Row has {} cells, but the first row has {}", row.len(), width))
+                    .in_line(y + 1));
+            }
+            cells.extend(row);
+        }
+        return Ok(Grid { width: width, height: height, cells: cells });
+    }
+
+    /// One row per line, `parse` turns each char into a cell, None rejects the char.
+    pub fn parse_with<F>(text: &str, parse: F) -> Result<Grid<T>, ParseError>
+        where F: Fn(char) -> Option<T> {
+        let rows = text.lines()
+            .enumerate()
+            .map(|(y, line)| line.chars()
+                .enumerate()
+                .map(|(x, char)| parse(char)
+                    .ok_or_else(|| Point::new(y, x).parse_error(format!("Unexpected '{}'", char))))
+                .collect::<Result<Vec<T>, ParseError>>())
+            .collect::<Result<Vec<Vec<T>>, ParseError>>()?;
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.y < self.height && point.x < self.width
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        if !self.contains(point) {
+            return None;
+        }
+        return Some(point.y * self.width + point.x);
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Returns the replaced value, None if `point` is outside and nothing was set.
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.get_mut(point).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Every point in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(y, x)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The up to 4 points sharing an edge with `point`.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::STRAIGHT)
+    }
+
+    /// The up to 8 points sharing an edge or a corner with `point`.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ALL)
+    }
+
+    fn neighbours<'a>(&'a self, point: Point, directions: &'static [Direction]) -> impl Iterator<Item = Point> + 'a {
+        directions.iter()
+            .filter_map(move |direction| point.step(*direction))
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// From `start` in `direction` up to the edge, empty if `start` is outside.
+    pub fn walk(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let first = Some(start).filter(|point| self.contains(*point));
+        iter::successors(first, move |point| point.step(direction).filter(|next| self.contains(*next)))
+            .map(|point| (point, &self.cells[point.y * self.width + point.x]))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        //chunks panics on 0, an empty grid has no rows anyway
+        self.cells.chunks(cmp::max(self.width, 1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.walk(Point::new(0, x), Direction::Down).map(|(_, cell)| cell)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Diagonals running down to the right, from the bottom left corner to the top right one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        let left_column = (0..self.height).rev().map(|y| Point::new(y, 0));
+        let top_row = (1..self.width).map(|x| Point::new(0, x));
+        left_column.chain(top_row).map(move |start| self.walk(start, Direction::DownRight))
+    }
+
+    /// Diagonals running down to the left, from the top left corner to the bottom right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Point, &T)>> {
+        let right_column = self.width.checked_sub(1).into_iter()
+            .flat_map(move |x| (1..self.height).map(move |y| Point::new(y, x)));
+        let top_row = (0..self.width).map(|x| Point::new(0, x));
+        top_row.chain(right_column).map(move |start| self.walk(start, Direction::DownLeft))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid { width: width, height: height, cells: vec![value; width * height] }
+    }
+
+    /// Rows become columns, mirrored along the diagonal from the top left.
+    pub fn transpose(&self) -> Grid<T> {
+        self.rearranged(self.height, self.width, |point| Point::new(point.x, point.y))
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        //the last row becomes the first column
+        self.rearranged(self.height, self.width, |point| Point::new(self.height - 1 - point.x, point.y))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        self.rearranged(self.height, self.width, |point| Point::new(point.x, self.width - 1 - point.y))
+    }
+
+    /// A `width` x `height` grid, taking each cell from the point `source` returns.
+    fn rearranged<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+        where F: Fn(Point) -> Point {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(y, x)))
+            .map(|point| self.cells[self.index(source(point)).expect("rearranged points stay inside")].clone())
+            .collect();
+        Grid { width: width, height: height, cells: cells }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(text, Some)
+    }
+}
+
+/// One line per row without trailing newline, so a `Grid<char>` prints the text it was parsed from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\n\
+         def".parse().unwrap()
+    }
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "467..114..\n\
+                    ...*......\n\
+                    ..35..633.";
+        let grid: Grid<char> = text.parse().unwrap();
+        assert_eq!((10, 3), (grid.width(), grid.height()));
+        assert_eq!(Some(&'*'), grid.get(Point::new(1, 3)));
+        assert_eq!(text, grid.to_string());
+        assert_eq!(text, format!("{}\n", text).parse::<Grid<char>>().unwrap().to_string());
+
+        let empty: Grid<char> = "".parse().unwrap();
+        assert_eq!((0, 0, ""), (empty.width(), empty.height(), empty.to_string().as_str()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "abc\nde".parse::<Grid<char>>().unwrap_err();
+        assert_eq!("line 2: Row has 2 cells, but the first row has 3", error.to_string());
+        let error = Grid::parse_with("12\n3x", |char| char.to_digit(10)).unwrap_err();
+        assert_eq!("line 2, column 2: Unexpected 'x'", error.to_string());
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = grid();
+        assert_eq!(Some(&'f'), grid.get(Point::new(1, 2)));
+        assert_eq!(None, grid.get(Point::new(1, 3)));
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+        assert_eq!(Some('f'), grid.set(Point::new(1, 2), 'x'));
+        assert_eq!(None, grid.set(Point::new(2, 2), 'y'));
+        assert_eq!("abc\ndex", grid.to_string());
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 'A';
+        assert_eq!("Abc\ndex", grid.to_string());
+        assert_eq!("..\n..", Grid::new(2, 2, '.').to_string());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let corner: Vec<Point> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(vec![Point::new(0, 1), Point::new(1, 0)], corner);
+        let middle: Vec<Point> = grid.neighbours8(Point::new(0, 1)).collect();
+        assert_eq!(vec![Point::new(0, 0), Point::new(0, 2), Point::new(1, 0), Point::new(1, 1), Point::new(1, 2)], middle);
+        assert_eq!(3, grid.neighbours8(Point::new(1, 2)).count());
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(vec!["abc", "def"], rows);
+        assert_eq!(Some(&['d', 'e', 'f'][..]), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        let columns: Vec<String> = grid.columns().map(collect).collect();
+        assert_eq!(vec!["ad", "be", "cf"], columns);
+        assert_eq!("", collect(grid.column(3)));
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = grid();
+        let diagonals: Vec<String> = grid.diagonals()
+            .map(|diagonal| diagonal.map(|(_, cell)| cell).collect())
+            .collect();
+        assert_eq!(vec!["d", "ae", "bf", "c"], diagonals);
+        let anti_diagonals: Vec<String> = grid.anti_diagonals()
+            .map(|diagonal| diagonal.map(|(_, cell)| cell).collect())
+            .collect();
+        assert_eq!(vec!["a", "bd", "ce", "f"], anti_diagonals);
+
+        let walked: Vec<(Point, &char)> = grid.walk(Point::new(1, 2), Direction::Left).collect();
+        assert_eq!(vec![(Point::new(1, 2), &'f'), (Point::new(1, 1), &'e'), (Point::new(1, 0), &'d')], walked);
+        assert_eq!(0, grid.walk(Point::new(5, 0), Direction::Up).count());
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = grid();
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_counter_clockwise().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counter_clockwise());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_clockwise().rotate_clockwise().rotate_clockwise());
+        assert_eq!(grid, grid.transpose().transpose());
+    }
+
+    #[test]
+    fn test_surrounding_range() {
+        assert_eq!((Point::new(1, 1), Point::new(3, 4)), Point::new(2, 2).surrounding_range(2));
+        assert_eq!((Point::new(0, 0), Point::new(1, 3)), Point::new(0, 0).surrounding_range(3));
+        assert_eq!(None, Point::new(0, 3).step(Direction::UpRight));
+        assert_eq!(Some(Point::new(1, 2)), Point::new(0, 3).step(Direction::DownLeft));
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+
+use super::{Direction, Grid, Point};
+
+/// Which cells count as connected when growing a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Left and right only, regions are runs within a line, like the digits of a number.
+    Horizontal,
+    /// Sharing an edge.
+    Four,
+    /// Sharing an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    fn directions(&self) -> &'static [Direction] {
+        match self {
+            Connectivity::Horizontal => &[Direction::Left, Direction::Right],
+            Connectivity::Four => &Direction::STRAIGHT,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Connected cells with the same label, e.g. the digits of one number in a schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Never empty, in reading order.
+    cells: Vec<Point>,
+}
+
+impl Region {
+    pub fn cells(&self) -> &[Point] {
+        &self.cells
+    }
+
+    /// The first cell in reading order, for a horizontal run its left end.
+    pub fn start(&self) -> Point {
+        self.cells[0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.binary_search(&point).is_ok()
+    }
+
+    /// Top left and bottom right corner of the smallest box around all cells.
+    pub fn bounding_box(&self) -> (Point, Point) {
+        let top = self.cells[0].y;
+        let bottom = self.cells[self.cells.len() - 1].y;
+        let left = self.cells.iter().map(|cell| cell.x).min().unwrap_or(0);
+        let right = self.cells.iter().map(|cell| cell.x).max().unwrap_or(0);
+        (Point::new(top, left), Point::new(bottom, right))
+    }
+
+    /// The cells' values in `grid`, in reading order.
+    /// For a region of a `Grid<char>` collect them into a `String` to get its text.
+    pub fn values<'a, T>(&'a self, grid: &'a Grid<T>) -> impl Iterator<Item = &'a T> {
+        self.cells.iter().filter_map(|cell| grid.get(*cell))
+    }
+}
+
+/// The labelled regions of a grid, see `Grid::regions`.
+///
+/// Regions are identified by their index, in reading order of their start.
+#[derive(Debug, Clone)]
+pub struct Regions<L> {
+    regions: Vec<(L, Region)>,
+    ids: Grid<Option<usize>>,
+}
+
+impl<L> Regions<L> {
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Id, label and region, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &L, &Region)> {
+        self.regions.iter()
+            .enumerate()
+            .map(|(id, (label, region))| (id, label, region))
+    }
+
+    pub fn label(&self, id: usize) -> &L {
+        &self.regions[id].0
+    }
+
+    pub fn region(&self, id: usize) -> &Region {
+        &self.regions[id].1
+    }
+
+    /// Id of the region covering `point`, None for unlabelled cells and points outside.
+    pub fn id_at(&self, point: Point) -> Option<usize> {
+        self.ids.get(point).copied().flatten()
+    }
+
+    /// Ids of the other regions with a cell next to one of region `id`'s cells.
+    pub fn touching(&self, id: usize, connectivity: Connectivity) -> impl Iterator<Item = usize> {
+        let touching: BTreeSet<usize> = self.region(id).cells.iter()
+            .flat_map(|cell| self.ids.neighbours(*cell, connectivity.directions()))
+            .filter_map(|neighbour| self.id_at(neighbour))
+            .filter(|other| *other != id)
+            .collect();
+        touching.into_iter()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into regions of connected cells with the same label.
+    /// Cells `label` maps to None belong to no region, like the dots between numbers.
+    pub fn regions<L, F>(&self, connectivity: Connectivity, label: F) -> Regions<L>
+        where L: PartialEq, F: Fn(&T) -> Option<L> {
+        let mut labels: Vec<Option<L>> = self.cells.iter().map(label).collect();
+        let mut ids: Grid<Option<usize>> = Grid::new(self.width, self.height, None);
+        let mut regions: Vec<(L, Region)> = vec![];
+
+        for start in self.points() {
+            let start_idx = start.y * self.width + start.x;
+            if ids.cells[start_idx].is_some() || labels[start_idx].is_none() {
+                continue;
+            }
+
+            //flood fill from the start, the first unassigned cell in reading order
+            let id = regions.len();
+            ids.cells[start_idx] = Some(id);
+            let mut cells = vec![start];
+            let mut todo = vec![start];
+            while let Some(point) = todo.pop() {
+                for neighbour in self.neighbours(point, connectivity.directions()) {
+                    let idx = neighbour.y * self.width + neighbour.x;
+                    if ids.cells[idx].is_none() && labels[idx].is_some() && labels[idx] == labels[start_idx] {
+                        ids.cells[idx] = Some(id);
+                        cells.push(neighbour);
+                        todo.push(neighbour);
+                    }
+                }
+            }
+            cells.sort();
+
+            //only the start's label is moved into the region, the other cells are assigned already
+            let label = labels[start_idx].take().expect("start is labelled");
+            regions.push((label, Region { cells: cells }));
+        }
+
+        return Regions { regions: regions, ids: ids };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Kind {
+        Digit,
+        Symbol,
+    }
+
+    fn kind(char: &char) -> Option<Kind> {
+        match char {
+            '.' => None,
+            char if char.is_ascii_digit() => Some(Kind::Digit),
+            _ => Some(Kind::Symbol),
+        }
+    }
+
+    fn describe(grid: &Grid<char>, regions: &Regions<Kind>, ids: impl Iterator<Item = usize>) -> Vec<String> {
+        ids.map(|id| format!("{} {:?} {}", regions.region(id).start(), regions.label(id), regions.region(id).values(grid).collect::<String>()))
+            .collect()
+    }
+
+    #[test]
+    fn test_horizontal_runs() {
+        let grid: Grid<char> = "467..114..\n\
+                                ...*......\n\
+                                ..35.#633.".parse().unwrap();
+        let regions = grid.regions(Connectivity::Horizontal, kind);
+        assert_eq!(vec![
+            "y:0 x:0 Digit 467",
+            "y:0 x:5 Digit 114",
+            "y:1 x:3 Symbol *",
+            "y:2 x:2 Digit 35",
+            "y:2 x:5 Symbol #",
+            "y:2 x:6 Digit 633",
+        ], describe(&grid, &regions, 0..regions.len()));
+
+        assert_eq!(Some(3), regions.id_at(Point::new(2, 3)));
+        assert_eq!(None, regions.id_at(Point::new(2, 4)));
+        assert_eq!(None, regions.id_at(Point::new(9, 9)));
+        assert_eq!((Point::new(0, 5), Point::new(0, 7)), regions.region(1).bounding_box());
+        assert!(regions.region(1).contains(Point::new(0, 6)));
+        assert!(!regions.region(1).contains(Point::new(1, 6)));
+    }
+
+    #[test]
+    fn test_touching() {
+        let grid: Grid<char> = "467..114..\n\
+                                ...*......\n\
+                                ..35.#633.".parse().unwrap();
+        let regions = grid.regions(Connectivity::Horizontal, kind);
+        //the * touches 467 and 35 diagonally, 114 not
+        assert_eq!(vec!["y:0 x:0 Digit 467", "y:2 x:2 Digit 35"],
+            describe(&grid, &regions, regions.touching(2, Connectivity::Eight)));
+        assert_eq!(vec!["y:2 x:6 Digit 633"], describe(&grid, &regions, regions.touching(4, Connectivity::Four)));
+        assert_eq!(0, regions.touching(1, Connectivity::Eight).count());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let grid: Grid<char> = "##..#\n\
+                                .#..#\n\
+                                ..#..".parse().unwrap();
+        let four = grid.regions(Connectivity::Four, kind);
+        let sizes: Vec<usize> = four.iter().map(|(_, _, region)| region.len()).collect();
+        assert_eq!(vec![3, 2, 1], sizes);
+        assert_eq!((Point::new(0, 0), Point::new(1, 1)), four.region(0).bounding_box());
+
+        //diagonally connected, the first one grows
+        let eight = grid.regions(Connectivity::Eight, kind);
+        let sizes: Vec<usize> = eight.iter().map(|(_, _, region)| region.len()).collect();
+        assert_eq!(vec![4, 2], sizes);
+        assert_eq!(vec![Point::new(0, 0), Point::new(0, 1), Point::new(1, 1), Point::new(2, 2)], eight.region(0).cells());
+    }
+
+    #[test]
+    fn test_labels_split_regions() {
+        let grid: Grid<char> = "12#\n\
+                                3##".parse().unwrap();
+        let regions = grid.regions(Connectivity::Four, kind);
+        assert_eq!(vec!["y:0 x:0 Digit 123", "y:0 x:2 Symbol ###"], describe(&grid, &regions, 0..regions.len()));
+    }
+}
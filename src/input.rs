@@ -0,0 +1,156 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Overrides the default input directory, same as `--input-dir`.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// The `res` directory of this crate, so the binary finds its inputs from any working directory.
+pub const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/res");
+
+/// Where the puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// Root with one folder per year: `<root>/y2023/day7.txt`.
+    Dir(PathBuf),
+    /// The same file for every selected part.
+    File(PathBuf),
+    /// Standard input, read once and shared by every selected part.
+    Stdin,
+}
+
+impl InputSource {
+    /// Prefers an explicit file (`-` for stdin), then the given directory,
+    /// then `AOC_INPUT_DIR` and finally the crate's `res` directory.
+    pub fn resolve(file: Option<PathBuf>, dir: Option<PathBuf>) -> InputSource {
+        if let Some(file) = file {
+            if file.as_os_str() == "-" {
+                return InputSource::Stdin;
+            }
+            return InputSource::File(file);
+        }
+        let dir = dir
+            .or_else(|| env::var_os(INPUT_DIR_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR));
+        return InputSource::Dir(dir);
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(PathBuf),
+    Read(PathBuf, io::Error),
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound(path) => write!(f, "input file {} not found", path.display()),
+            InputError::Read(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            InputError::Stdin(e) => write!(f, "could not read stdin: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::NotFound(_) => None,
+            InputError::Read(_, e) | InputError::Stdin(e) => Some(e),
+        }
+    }
+}
+
+pub struct InputLoader {
+    source: InputSource,
+    stdin: OnceLock<String>,
+}
+
+impl InputLoader {
+    pub fn new(source: InputSource) -> Self {
+        InputLoader { source: source, stdin: OnceLock::new() }
+    }
+
+    pub fn source(&self) -> &InputSource {
+        &self.source
+    }
+
+    pub fn load(&self, year: u16, day: u8, part: u8) -> Result<String, InputError> {
+        match &self.source {
+            InputSource::Dir(root) => read(&find_input(root, year, day, part)),
+            InputSource::File(path) => read(path),
+            InputSource::Stdin => {
+                if let Some(input) = self.stdin.get() {
+                    return Ok(input.clone());
+                }
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map_err(InputError::Stdin)?;
+                return Ok(self.stdin.get_or_init(|| input).clone());
+            }
+        }
+    }
+}
+
+/// `<root>/y<year>/day<day>.txt` holds the input of both parts, a part that
+/// needs its own input (2023 day 1) uses `day<day>_<part>.txt` instead.
+/// Returns the shared file name if neither exists, so errors name the expected path.
+pub fn find_input(root: &Path, year: u16, day: u8, part: u8) -> PathBuf {
+    find_day_file(root, year, day, part, ".txt")
+}
+
+/// Same lookup as `find_input` for other files of a day, e.g. `.example.txt`.
+pub fn find_day_file(root: &Path, year: u16, day: u8, part: u8, suffix: &str) -> PathBuf {
+    let dir = root.join(format!("y{}", year));
+    let part_specific = dir.join(format!("day{}_{}{}", day, part, suffix));
+    if part_specific.is_file() {
+        return part_specific;
+    }
+    return dir.join(format!("day{}{}", day, suffix));
+}
+
+pub fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound(path.to_path_buf()),
+        _ => InputError::Read(path.to_path_buf(), e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::temp_dir_with;
+
+    #[test]
+    fn test_find_input() {
+        let root = temp_dir_with("find_input", &[("y2023/day1.txt", "1abc2"), ("y2023/day1_2.txt", "two1nine")]);
+        assert_eq!(root.join("y2023/day1_2.txt"), find_input(&root, 2023, 1, 2));
+        assert_eq!(root.join("y2023/day1.txt"), find_input(&root, 2023, 1, 1));
+        assert_eq!(root.join("y2023/day7.txt"), find_input(&root, 2023, 7, 2));
+        assert_eq!(root.join("y2023/day25.txt"), find_input(&root, 2023, 25, 1));
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(InputSource::Stdin, InputSource::resolve(Some(PathBuf::from("-")), Some(PathBuf::from("in"))));
+        assert_eq!(InputSource::File(PathBuf::from("a.txt")), InputSource::resolve(Some(PathBuf::from("a.txt")), None));
+        assert_eq!(InputSource::Dir(PathBuf::from("in")), InputSource::resolve(None, Some(PathBuf::from("in"))));
+    }
+
+    #[test]
+    fn test_load() {
+        let root = temp_dir_with("load", &[("y2024/day1.txt", "3   4\n4   3")]);
+        let loader = InputLoader::new(InputSource::Dir(root.clone()));
+        assert!(loader.load(2024, 1, 2).unwrap().starts_with("3   4"));
+
+        let missing = root.join("y2023/day25.txt");
+        match loader.load(2023, 25, 1) {
+            Err(InputError::NotFound(path)) => assert_eq!(missing, path),
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+        assert!(loader.load(2023, 25, 1).unwrap_err().to_string().contains("day25.txt"));
+    }
+}
@@ -0,0 +1,490 @@
+use std::fmt;
+
+/// The integers `start..end`, the end is exclusive.
+///
+/// Puzzles usually give ranges as start and length (`Interval::with_length`)
+/// or first and last value (`Interval::inclusive`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Interval {
+        Interval { start: start, end: end }
+    }
+
+    pub fn inclusive(first: i64, last: i64) -> Interval {
+        Interval::new(first, last + 1)
+    }
+
+    pub fn with_length(start: i64, length: i64) -> Interval {
+        Interval::new(start, start + length)
+    }
+
+    /// The last value inside, only meaningful if not empty.
+    pub fn last(&self) -> i64 {
+        self.end - 1
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: Interval) -> Option<Interval> {
+        let intersection = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        if intersection.is_empty() {
+            return None;
+        }
+        return Some(intersection);
+    }
+
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers stored as sorted intervals.
+///
+/// Intervals are coalesced: none is empty, and neither overlapping nor adjacent intervals
+/// are kept apart, so `0..3` and `3..5` are stored as `0..5`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    /// Number of intervals, see `count` for the number of values.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn count(&self) -> i64 {
+        self.intervals.iter().map(|interval| interval.len()).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.last())
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let idx = self.intervals.partition_point(|interval| interval.end <= value);
+        self.intervals.get(idx).is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        *self = self.union(&IntervalSet::from(interval));
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(intersection) = a.intersection(b) {
+                intervals.push(intersection);
+            }
+            //the one ending first can't intersect anything after the other one
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        //both inputs are coalesced, so are their intersections
+        return IntervalSet { intervals: intervals };
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let mut j = 0;
+        for interval in self.iter() {
+            let mut rest = interval;
+            //skip everything of other ending before this interval
+            while j < other.intervals.len() && other.intervals[j].end <= rest.start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < rest.end {
+                let cut = other.intervals[k];
+                if cut.start > rest.start {
+                    intervals.push(Interval::new(rest.start, cut.start));
+                }
+                rest = Interval::new(cut.end.max(rest.start), rest.end);
+                if rest.is_empty() {
+                    break;
+                }
+                k += 1;
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        return IntervalSet { intervals: intervals };
+    }
+
+    /// Moves every value by `offset`.
+    pub fn shift(&self, offset: i64) -> IntervalSet {
+        IntervalSet { intervals: self.iter().map(|interval| interval.shift(offset)).collect() }
+    }
+
+    /// The parts of the set inside `bounds`, in order.
+    fn clipped(&self, bounds: Interval) -> impl Iterator<Item = Interval> + '_ {
+        let first = self.intervals.partition_point(|interval| interval.end <= bounds.start);
+        self.intervals[first..].iter()
+            .take_while(move |interval| interval.start < bounds.end)
+            .filter_map(move |interval| interval.intersection(bounds))
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut sorted: Vec<Interval> = iter.into_iter().filter(|interval| !interval.is_empty()).collect();
+        sorted.sort();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => intervals.push(interval),
+            }
+        }
+        return IntervalSet { intervals: intervals };
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let intervals: Vec<String> = self.iter().map(|interval| interval.to_string()).collect();
+        write!(f, "{{{}}}", intervals.join(", "))
+    }
+}
+
+/// Values inside `source` move by `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub source: Interval,
+    pub offset: i64,
+}
+
+impl Rule {
+    /// From the almanac's notation, `50 98 2` maps 98 to 50 and 99 to 51.
+    pub fn new(destination: i64, source: i64, length: i64) -> Rule {
+        Rule { source: Interval::with_length(source, length), offset: destination - source }
+    }
+
+    pub fn destination(&self) -> Interval {
+        self.source.shift(self.offset)
+    }
+}
+
+/// A piecewise shift of the integers: values inside a rule's source move by its offset,
+/// all other values map to themselves.
+///
+/// The rules are kept sorted by source and never overlap, so single values map in O(log n)
+/// and interval sets in one pass over their intervals per rule.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    rules: Vec<Rule>,
+}
+
+impl RangeMap {
+    pub fn new() -> RangeMap {
+        RangeMap::default()
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Adds the rule `destination source length`.
+    /// Where its source overlaps earlier rules, the earlier ones win.
+    pub fn push(&mut self, destination: i64, source: i64, length: i64) {
+        let rule = Rule::new(destination, source, length);
+        let uncovered = IntervalSet::from(rule.source).difference(&self.sources());
+        for source in uncovered.iter() {
+            self.rules.push(Rule { source: source, offset: rule.offset });
+        }
+        self.rules.sort_by_key(|rule| rule.source);
+    }
+
+    /// All values moved by some rule.
+    pub fn sources(&self) -> IntervalSet {
+        self.rules.iter().map(|rule| rule.source).collect()
+    }
+
+    pub fn get(&self, value: i64) -> i64 {
+        let idx = self.rules.partition_point(|rule| rule.source.end <= value);
+        match self.rules.get(idx) {
+            Some(rule) if rule.source.contains(value) => value + rule.offset,
+            _ => value,
+        }
+    }
+
+    /// All values `get` returns for the values of `set`.
+    pub fn map(&self, set: &IntervalSet) -> IntervalSet {
+        let moved = self.rules.iter()
+            .flat_map(|rule| set.clipped(rule.source).map(|interval| interval.shift(rule.offset)));
+        let unmoved = set.difference(&self.sources());
+        moved.chain(unmoved.iter()).collect()
+    }
+
+    /// This map followed by `next` as one map, `a.then(&b).get(x) == b.get(a.get(x))`.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let sources = self.sources();
+        let next_sources = next.sources();
+        let mut rules: Vec<Rule> = vec![];
+        for rule in &self.rules {
+            //split where the moved values land in the rules of next
+            let destination = rule.destination();
+            for next_rule in next.overlapping(destination) {
+                if let Some(hit) = next_rule.source.intersection(destination) {
+                    rules.push(Rule { source: hit.shift(-rule.offset), offset: rule.offset + next_rule.offset });
+                }
+            }
+            for missed in IntervalSet::from(destination).difference(&next_sources).iter() {
+                rules.push(Rule { source: missed.shift(-rule.offset), offset: rule.offset });
+            }
+        }
+        //values this map leaves alone only move in next
+        for next_rule in &next.rules {
+            for unmoved in IntervalSet::from(next_rule.source).difference(&sources).iter() {
+                rules.push(Rule { source: unmoved, offset: next_rule.offset });
+            }
+        }
+        return RangeMap::from_disjoint(rules);
+    }
+
+    /// The map undoing this one, None if it maps two values to the same value.
+    ///
+    /// That's the case if the destinations of two rules overlap, or if a rule moves values
+    /// onto ones no rule moves, as those still map to themselves.
+    pub fn inverse(&self) -> Option<RangeMap> {
+        let sources = self.sources();
+        let destinations: IntervalSet = self.rules.iter().map(|rule| rule.destination()).collect();
+        //overlapping destinations cover fewer values than the disjoint sources,
+        //and covering other values than the sources hits unmoved ones
+        if destinations.count() != sources.count() || destinations != sources {
+            return None;
+        }
+        let rules = self.rules.iter()
+            .map(|rule| Rule { source: rule.destination(), offset: -rule.offset })
+            .collect();
+        return Some(RangeMap::from_disjoint(rules));
+    }
+
+    /// Rules with a source overlapping `bounds`, in order.
+    fn overlapping(&self, bounds: Interval) -> impl Iterator<Item = &Rule> + '_ {
+        let first = self.rules.partition_point(|rule| rule.source.end <= bounds.start);
+        self.rules[first..].iter().take_while(move |rule| rule.source.start < bounds.end)
+    }
+
+    /// Sorts rules with disjoint sources, drops the ones not moving anything
+    /// and merges neighbours moving by the same offset.
+    fn from_disjoint(mut rules: Vec<Rule>) -> RangeMap {
+        rules.retain(|rule| rule.offset != 0 && !rule.source.is_empty());
+        rules.sort_by_key(|rule| rule.source);
+
+        let mut merged: Vec<Rule> = Vec::with_capacity(rules.len());
+        for rule in rules {
+            match merged.last_mut() {
+                Some(last) if last.source.end == rule.source.start && last.offset == rule.offset => last.source.end = rule.source.end,
+                _ => merged.push(rule),
+            }
+        }
+        return RangeMap { rules: merged };
+    }
+}
+
+impl FromIterator<(i64, i64, i64)> for RangeMap {
+    /// From `(destination, source, length)` rules.
+    fn from_iter<I: IntoIterator<Item = (i64, i64, i64)>>(iter: I) -> Self {
+        let mut map = RangeMap::new();
+        for (destination, source, length) in iter {
+            map.push(destination, source, length);
+        }
+        return map;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals.iter().map(|(first, last)| Interval::inclusive(*first, *last)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::with_length(79, 14);
+        assert_eq!(Interval::inclusive(79, 92), interval);
+        assert_eq!(14, interval.len());
+        assert_eq!(92, interval.last());
+        assert!(interval.contains(79) && interval.contains(92) && !interval.contains(93));
+        assert_eq!(Some(Interval::new(85, 93)), interval.intersection(Interval::new(85, 100)));
+        assert_eq!(None, interval.intersection(Interval::new(93, 100)));
+        assert!(Interval::new(3, 3).is_empty());
+        assert_eq!("79..93", interval.to_string());
+    }
+
+    #[test]
+    fn test_coalescing() {
+        let set: IntervalSet = vec![
+            Interval::new(10, 15),
+            Interval::new(0, 3),
+            Interval::new(3, 5),
+            Interval::new(12, 20),
+            Interval::new(7, 7),
+        ].into_iter().collect();
+        assert_eq!(&[Interval::new(0, 5), Interval::new(10, 20)], set.intervals());
+        assert_eq!(15, set.count());
+        assert_eq!((Some(0), Some(19)), (set.min(), set.max()));
+        assert!(set.contains(4) && set.contains(10) && !set.contains(5) && !set.contains(20));
+        assert_eq!("{0..5, 10..20}", set.to_string());
+
+        let mut inserted = set.clone();
+        inserted.insert(Interval::new(5, 10));
+        assert_eq!(&[Interval::new(0, 20)], inserted.intervals());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 9), (20, 29), (40, 49)]);
+        let b = set(&[(5, 24), (45, 45), (60, 69)]);
+        assert_eq!(set(&[(0, 29), (40, 49), (60, 69)]), a.union(&b));
+        assert_eq!(set(&[(5, 9), (20, 24), (45, 45)]), a.intersection(&b));
+        assert_eq!(set(&[(0, 4), (25, 29), (40, 44), (46, 49)]), a.difference(&b));
+        assert_eq!(set(&[(10, 19), (60, 69)]), b.difference(&a));
+        assert_eq!(set(&[(-5, 4), (15, 24), (35, 44)]), a.shift(-5));
+
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+        assert_eq!(a, a.difference(&IntervalSet::new()));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_range_map_cases() {
+        //cases: i - input, s - source of the rule
+        //..iiii....  ...iiii...  ..iiiiii..  .....iii..
+        //....ssss..  ..ssssss..  ...ssss...  ..sss.....
+        let map: RangeMap = vec![(120, 20, 30)].into_iter().collect();
+        assert_eq!(set(&[(10, 15)]), map.map(&set(&[(10, 15)])));
+        assert_eq!(set(&[(15, 19), (120, 125)]), map.map(&set(&[(15, 25)])));
+        assert_eq!(set(&[(125, 145)]), map.map(&set(&[(25, 45)])));
+        assert_eq!(set(&[(50, 55), (145, 149)]), map.map(&set(&[(45, 55)])));
+        assert_eq!(set(&[(10, 19), (50, 60), (120, 149)]), map.map(&set(&[(10, 60)])));
+        assert_eq!(set(&[(130, 130)]), map.map(&set(&[(30, 30)])));
+
+        assert_eq!(19, map.get(19));
+        assert_eq!(120, map.get(20));
+        assert_eq!(149, map.get(49));
+        assert_eq!(50, map.get(50));
+    }
+
+    #[test]
+    fn test_range_map_overlapping_rules() {
+        //the almanac's seed-to-soil map, plus a rule overlapping both
+        let map: RangeMap = vec![(50, 98, 2), (52, 50, 48), (0, 40, 100)].into_iter().collect();
+        assert_eq!(vec![
+            Rule::new(0, 40, 10),
+            Rule::new(52, 50, 48),
+            Rule::new(50, 98, 2),
+            Rule::new(60, 100, 40),
+        ], map.rules());
+        assert_eq!(50, map.get(98));
+        assert_eq!(81, map.get(79));
+        assert_eq!(5, map.get(45));
+        assert_eq!(Interval::new(50, 52), map.rules()[2].destination());
+
+        let seeds = set(&[(79, 92), (55, 67)]);
+        assert_eq!(set(&[(57, 69), (81, 94)]), map.map(&seeds));
+    }
+
+    #[test]
+    fn test_range_map_then() {
+        let seed_to_soil: RangeMap = vec![(50, 98, 2), (52, 50, 48)].into_iter().collect();
+        let soil_to_fertilizer: RangeMap = vec![(0, 15, 37), (37, 52, 2), (39, 0, 15)].into_iter().collect();
+        let seed_to_fertilizer = seed_to_soil.then(&soil_to_fertilizer);
+        for seed in -10..120 {
+            assert_eq!(soil_to_fertilizer.get(seed_to_soil.get(seed)), seed_to_fertilizer.get(seed), "seed {}", seed);
+        }
+        assert_eq!(81, seed_to_fertilizer.get(79));
+        assert_eq!(53, seed_to_fertilizer.get(14));
+
+        //composing with the identity changes nothing
+        assert_eq!(seed_to_fertilizer, seed_to_fertilizer.then(&RangeMap::new()));
+        assert_eq!(seed_to_fertilizer, RangeMap::new().then(&seed_to_fertilizer));
+
+        //mapping a set through both equals mapping it through the composition
+        let seeds = set(&[(79, 92), (55, 67), (0, 20)]);
+        assert_eq!(soil_to_fertilizer.map(&seed_to_soil.map(&seeds)), seed_to_fertilizer.map(&seeds));
+    }
+
+    #[test]
+    fn test_range_map_inverse() {
+        let seed_to_soil: RangeMap = vec![(50, 98, 2), (52, 50, 48)].into_iter().collect();
+        let soil_to_seed = seed_to_soil.inverse().unwrap();
+        assert_eq!(98, soil_to_seed.get(50));
+        assert_eq!(53, soil_to_seed.get(55));
+        assert_eq!(10, soil_to_seed.get(10));
+        assert!(seed_to_soil.then(&soil_to_seed).rules().is_empty());
+        assert_eq!(Some(seed_to_soil.clone()), soil_to_seed.inverse());
+
+        //52 gets hit by 50 and by the unmoved 52
+        let merging: RangeMap = vec![(52, 50, 2)].into_iter().collect();
+        assert_eq!(None, merging.inverse());
+        //two rules onto the same destination
+        let merging: RangeMap = vec![(0, 10, 5), (0, 20, 5)].into_iter().collect();
+        assert_eq!(None, merging.inverse());
+        //overlapping destinations within the sources
+        let merging: RangeMap = vec![(10, 12, 5), (12, 17, 5)].into_iter().collect();
+        assert_eq!(None, merging.inverse());
+    }
+}
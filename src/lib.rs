@@ -0,0 +1,21 @@
+//explicit returns, `id: id` initialisers and bit groups matching card positions (0b100_11_10_1) are house style
+#![allow(clippy::needless_return, clippy::redundant_field_names, clippy::unusual_byte_groupings)]
+
+pub mod answers;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod measure;
+pub mod parallel;
+pub mod progress;
+pub mod runner;
+pub mod solution;
+pub mod table;
+pub mod testutil;
+pub mod verify;
+pub mod y2023;
+pub mod y2024;
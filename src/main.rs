@@ -1,6 +1,124 @@
-mod y2023;
-mod y2024;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
 
-fn main() -> std::io::Result<()> {
-    y2024::day2_2::run()
-}
\ No newline at end of file
+use peak_alloc::PeakAlloc;
+use rust_aoc::answers::{answers_path, Answers};
+use rust_aoc::cli::{self, Args, Mode};
+use rust_aoc::input::{InputLoader, InputSource};
+use rust_aoc::measure::{self, Measurement};
+use rust_aoc::runner::{self, watched};
+use rust_aoc::solution::{self, Solution};
+use rust_aoc::verify::{self, Check, Status};
+
+//counts the allocations for the peak memory of each solution, see `measure`
+#[global_allocator]
+static PEAK_ALLOC: PeakAlloc = PeakAlloc;
+
+const SKIPPED: &str = "skipped after Ctrl-C";
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let solutions: Vec<&dyn Solution> = solution::all().into_iter()
+        .filter(|solution| args.matches(*solution))
+        .collect();
+    if solutions.is_empty() {
+        eprintln!("no solution registered for {}", args.selection());
+        return ExitCode::FAILURE;
+    }
+
+    let loader = InputLoader::new(InputSource::resolve(args.input.clone(), args.input_dir.clone()));
+    log::debug!("reading inputs from {:?}", loader.source());
+
+    if let Err(e) = ctrlc::set_handler(runner::interrupt) {
+        log::warn!("Ctrl-C can't cancel solutions: {}", e);
+    }
+
+    match args.mode {
+        Mode::Run => run(&solutions, &loader, args.json, args.timeout),
+        Mode::Verify => verify(&solutions, &loader, args.timeout),
+    }
+}
+
+fn run(solutions: &[&dyn Solution], loader: &InputLoader, json: bool, timeout: Option<Duration>) -> ExitCode {
+    let mut measurements: Vec<Measurement> = vec![];
+    for &solution in solutions {
+        if runner::is_interrupted() {
+            measurements.push(Measurement::failed(solution, SKIPPED.to_string()));
+            continue;
+        }
+        let measurement = match loader.load(solution.year(), solution.day(), solution.part()) {
+            Ok(input) => watched(solution, timeout, || measure::measure(solution, &input)),
+            Err(e) => Measurement::failed(solution, e.to_string()),
+        };
+        measurements.push(measurement);
+    }
+
+    if json {
+        println!("{}", measure::to_json(&measurements));
+    } else {
+        print!("{}", measure::format_table(&measurements));
+    }
+    if measurements.iter().any(|measurement| measurement.answer.is_err()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify(solutions: &[&dyn Solution], loader: &InputLoader, timeout: Option<Duration>) -> ExitCode {
+    //the accepted answers belong to the inputs in the input directory
+    let root = match loader.source() {
+        InputSource::Dir(root) => root,
+        _ => {
+            eprintln!("verify compares the inputs of an input directory with their answers, it can't be used with --input");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut answers: HashMap<u16, Answers> = HashMap::new();
+    let mut checks: Vec<Check> = vec![];
+    for &solution in solutions {
+        let year = solution.year();
+        if let Entry::Vacant(entry) = answers.entry(year) {
+            match Answers::load(&answers_path(root, year)) {
+                Ok(loaded) => entry.insert(loaded),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+        }
+
+        let actual = if runner::is_interrupted() {
+            Err(SKIPPED.to_string())
+        } else {
+            loader.load(year, solution.day(), solution.part())
+                .map_err(|e| e.to_string())
+                .and_then(|input| watched(solution, timeout, || solution.solve(&input)).map_err(|e| e.to_string()))
+        };
+        checks.push(Check::new(solution, actual, answers[&year].get(solution.day(), solution.part())));
+    }
+
+    println!("{}", verify::format_table(&checks));
+    if checks.iter().any(|check| check.status() == Status::Fail) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
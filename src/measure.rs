@@ -0,0 +1,271 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use peak_alloc::PeakAlloc;
+
+use crate::solution::{Answer, Solution};
+use crate::table::{self, Align};
+
+/// Reads the counters of `peak_alloc`. They only count if the binary installs
+/// `PeakAlloc` as its `#[global_allocator]`, as `rust_aoc` does, otherwise every peak is 0.
+const ALLOC: PeakAlloc = PeakAlloc;
+
+/// Time and memory of one solution on its input.
+///
+/// `solve` runs once, parse and solve are split where it calls `parsed`.
+/// Solutions without a parse step of their own, like picking numbers from the lines, parse in 0ns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The answer, or why there is none. Without answer the numbers below are 0.
+    pub answer: Result<Answer, String>,
+    /// Up to `parsed`.
+    pub parse_time: Duration,
+    pub total_time: Duration,
+    /// Most bytes allocated at once up to `parsed`, on top of what was allocated before.
+    pub parse_peak: usize,
+    /// Same for the complete `solve`.
+    pub peak: usize,
+}
+
+impl Measurement {
+    /// For a solution that didn't get to run, e.g. because its input is missing.
+    pub fn failed(solution: &dyn Solution, reason: String) -> Measurement {
+        Measurement {
+            year: solution.year(),
+            day: solution.day(),
+            part: solution.part(),
+            answer: Err(reason),
+            parse_time: Duration::ZERO,
+            total_time: Duration::ZERO,
+            parse_peak: 0,
+            peak: 0,
+        }
+    }
+
+    pub fn solve_time(&self) -> Duration {
+        self.total_time.saturating_sub(self.parse_time)
+    }
+}
+
+thread_local! {
+    //when and at which peak the running solution finished parsing
+    static PARSED: Cell<Option<(Instant, usize)>> = const { Cell::new(None) };
+}
+
+/// Marks the end of parsing in a `solve`, everything after it counts as solve time.
+pub fn parsed() {
+    PARSED.with(|parsed| parsed.set(Some((Instant::now(), ALLOC.peak_usage()))));
+}
+
+pub fn measure(solution: &dyn Solution, input: &str) -> Measurement {
+    PARSED.with(|parsed| parsed.set(None));
+    ALLOC.reset_peak_usage();
+    let baseline = ALLOC.current_usage();
+    let start = Instant::now();
+    let answer = solution.solve(input);
+    let total_time = start.elapsed();
+    let peak = ALLOC.peak_usage().saturating_sub(baseline);
+
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(e) => return Measurement::failed(solution, e.to_string()),
+    };
+    let (parse_time, parse_peak) = match PARSED.with(Cell::take) {
+        Some((parsed, parse_peak)) => (parsed.duration_since(start), parse_peak.saturating_sub(baseline)),
+        None => (Duration::ZERO, 0),
+    };
+
+    return Measurement {
+        year: solution.year(),
+        day: solution.day(),
+        part: solution.part(),
+        answer: Ok(answer),
+        parse_time: parse_time,
+        total_time: total_time,
+        parse_peak: parse_peak,
+        peak: peak,
+    };
+}
+
+pub fn format_table(measurements: &[Measurement]) -> String {
+    let columns = [
+        ("year", Align::Right),
+        ("day", Align::Right),
+        ("part", Align::Right),
+        ("answer", Align::Left),
+        ("parse", Align::Right),
+        ("solve", Align::Right),
+        ("total", Align::Right),
+        ("peak", Align::Right),
+    ];
+    let rows: Vec<Vec<String>> = measurements.iter()
+        .map(|measurement| {
+            let mut row = vec![
+                measurement.year.to_string(),
+                measurement.day.to_string(),
+                measurement.part.to_string(),
+            ];
+            match &measurement.answer {
+                Ok(answer) => row.extend([
+                    answer.to_string(),
+                    format!("{:?}", measurement.parse_time),
+                    format!("{:?}", measurement.solve_time()),
+                    format!("{:?}", measurement.total_time),
+                    format_bytes(measurement.peak),
+                ]),
+                Err(e) => row.push(format!("error: {}", e)),
+            }
+            row
+        })
+        .collect();
+    return table::render(&columns, &rows);
+}
+
+/// A JSON array with one object per measurement, durations in nanoseconds and memory in bytes:
+///
+/// ```json
+/// [
+///   {"year": 2023, "day": 7, "part": 1, "answer": 6440, "parse_ns": 5120, "solve_ns": 1310, "total_ns": 6430, "parse_peak_bytes": 320, "peak_bytes": 352},
+///   {"year": 2023, "day": 8, "part": 1, "error": "input file res/y2023/day8.txt not found"}
+/// ]
+/// ```
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let objects: Vec<String> = measurements.iter()
+        .map(|measurement| {
+            let key = format!("\"year\": {}, \"day\": {}, \"part\": {}",
+                measurement.year, measurement.day, measurement.part);
+            match &measurement.answer {
+                Ok(answer) => format!("  {{{}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"total_ns\": {}, \"parse_peak_bytes\": {}, \"peak_bytes\": {}}}",
+                    key,
+                    match answer {
+                        Answer::Number(number) => number.to_string(),
+                        Answer::Text(text) => json_string(text),
+                    },
+                    measurement.parse_time.as_nanos(),
+                    measurement.solve_time().as_nanos(),
+                    measurement.total_time.as_nanos(),
+                    measurement.parse_peak,
+                    measurement.peak),
+                Err(e) => format!("  {{{}, \"error\": {}}}", key, json_string(e)),
+            }
+        })
+        .collect();
+    if objects.is_empty() {
+        return "[]".to_string();
+    }
+    return format!("[\n{}\n]", objects.join(",\n"));
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    return quoted;
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KIB: usize = 1024;
+    const MIB: usize = 1024 * 1024;
+    match bytes {
+        0..KIB => format!("{} B", bytes),
+        KIB..MIB => format!("{:.1} KiB", bytes as f64 / KIB as f64),
+        _ => format!("{:.1} MiB", bytes as f64 / MIB as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::y2023;
+    use crate::y2024::day1_1;
+
+    struct SlowParser;
+
+    impl Solution for SlowParser {
+        fn year(&self) -> u16 { 2023 }
+        fn day(&self) -> u8 { 25 }
+        fn part(&self) -> u8 { 1 }
+        fn solve(&self, input: &str) -> Result<Answer, Error> {
+            std::thread::sleep(Duration::from_millis(20));
+            parsed();
+            Ok(Answer::from(input.len()))
+        }
+    }
+
+    fn measurement(answer: Result<Answer, String>) -> Measurement {
+        Measurement {
+            year: 2023,
+            day: 7,
+            part: 1,
+            answer: answer,
+            parse_time: Duration::from_micros(5),
+            total_time: Duration::from_micros(7),
+            parse_peak: 320,
+            peak: 2048,
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let measurement = measure(&day1_1::Solver, "1   3\n4   4");
+        assert_eq!(Ok(Answer::Number(2)), measurement.answer);
+
+        let measurement = measure(&day1_1::Solver, "3   x");
+        assert_eq!(Err("invalid input, line 1, column 5: 'x' is not a valid number".to_string()), measurement.answer);
+    }
+
+    #[test]
+    fn test_measure_parse_in_the_same_run() {
+        let measurement = measure(&SlowParser, "abc");
+        assert_eq!(Ok(Answer::Number(3)), measurement.answer);
+        assert!(measurement.parse_time >= Duration::from_millis(20), "{:?}", measurement);
+        assert!(measurement.parse_time <= measurement.total_time, "{:?}", measurement);
+
+        //no parse step of its own, a mark left over from outside a run doesn't count
+        parsed();
+        let measurement = measure(&y2023::day1_1::Solver, "a1b2c");
+        assert_eq!(Ok(Answer::Number(12)), measurement.answer);
+        assert_eq!(Duration::ZERO, measurement.parse_time);
+        assert_eq!(measurement.total_time, measurement.solve_time());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(&[measurement(Ok(Answer::Number(6440))), measurement(Err("no solution".to_string()))]);
+        let expected = "year day part  answer              parse solve total    peak\n\
+                        2023   7    1  6440                  5µs   2µs   7µs 2.0 KiB\n\
+                        2023   7    1  error: no solution\n";
+        assert_eq!(expected, table);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&[measurement(Ok(Answer::Text("a\"b".to_string()))), measurement(Err("line 1\tx".to_string()))]);
+        let expected = "[\n  \
+            {\"year\": 2023, \"day\": 7, \"part\": 1, \"answer\": \"a\\\"b\", \"parse_ns\": 5000, \"solve_ns\": 2000, \"total_ns\": 7000, \"parse_peak_bytes\": 320, \"peak_bytes\": 2048},\n  \
+            {\"year\": 2023, \"day\": 7, \"part\": 1, \"error\": \"line 1\\tx\"}\n\
+            ]";
+        assert_eq!(expected, json);
+        assert_eq!("[]", to_json(&[]));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("1023 B", format_bytes(1023));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 MiB", format_bytes(3 * 1024 * 1024));
+    }
+}
@@ -0,0 +1,198 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::interval::Interval;
+use crate::progress::{self, Cancelled, Progress};
+
+/// Values per chunk if not set otherwise, small enough to spread uneven ranges
+/// over the workers, large enough to make taking the next chunk negligible.
+pub const DEFAULT_CHUNK_SIZE: i64 = 1 << 16;
+
+/// Brute force helper, maps every value of some intervals on a fixed number of worker threads
+/// and reduces the results, e.g. to the smallest location of billions of seeds.
+///
+/// The intervals are cut into chunks, each worker takes the next chunk until none is left
+/// and reduces its results locally. Only the per worker results are reduced at the end,
+/// so `reduce` has to be associative and commutative, like `min` or `+`.
+///
+/// Progress is reported per chunk, and the workers stop before their next chunk once it's cancelled.
+#[derive(Debug, Clone)]
+pub struct Executor {
+    workers: usize,
+    chunk_size: i64,
+    progress: Arc<Progress>,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Executor {
+            workers: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            progress: progress::current(),
+        }
+    }
+}
+
+impl Executor {
+    /// One worker per available core.
+    pub fn new() -> Executor {
+        Executor::default()
+    }
+
+    pub fn with_workers(mut self, workers: usize) -> Executor {
+        self.workers = workers.max(1);
+        self
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: i64) -> Executor {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Reports into `progress` instead of the `progress::current` one of the creating thread.
+    pub fn with_progress(mut self, progress: Arc<Progress>) -> Executor {
+        self.progress = progress;
+        self
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// How far the current run is, can be watched and cancelled from another thread.
+    pub fn progress(&self) -> Arc<Progress> {
+        Arc::clone(&self.progress)
+    }
+
+    /// Folds `map` of every value in `ranges` into `identity` using `reduce`.
+    pub fn map_reduce<T, M, R>(&self, ranges: &[Interval], identity: T, map: M, reduce: R) -> Result<T, Cancelled>
+        where T: Clone + Send, M: Fn(i64) -> T + Sync, R: Fn(T, T) -> T + Sync {
+        let chunks = self.chunks(ranges);
+        self.progress.start(chunks.iter().map(|chunk| chunk.len() as u64).sum());
+
+        let next_chunk = AtomicUsize::new(0);
+        let workers = self.workers.min(chunks.len());
+        let (chunks, next_chunk, map, reduce, progress) = (&chunks, &next_chunk, &map, &reduce, &self.progress);
+        let results: Vec<T> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    let mut local = identity.clone();
+                    scope.spawn(move || {
+                        while !progress.is_cancelled() {
+                            let Some(chunk) = chunks.get(next_chunk.fetch_add(1, Ordering::Relaxed)) else {
+                                break;
+                            };
+                            for value in chunk.start..chunk.end {
+                                local = reduce(local, map(value));
+                            }
+                            progress.advance(chunk.len() as u64);
+                        }
+                        local
+                    })
+                })
+                .collect();
+            handles.into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+        //a cancel after the last chunk doesn't matter
+        if self.progress.done() < self.progress.total() {
+            self.progress.check()?;
+        }
+        return Ok(results.into_iter().fold(identity, reduce));
+    }
+
+    /// The smallest `map` of all values, None for empty ranges.
+    pub fn min<T, M>(&self, ranges: &[Interval], map: M) -> Result<Option<T>, Cancelled>
+        where T: Ord + Clone + Send, M: Fn(i64) -> T + Sync {
+        self.map_reduce(ranges, None, |value| Some(map(value)), |a, b| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    pub fn sum<M>(&self, ranges: &[Interval], map: M) -> Result<i64, Cancelled>
+        where M: Fn(i64) -> i64 + Sync {
+        self.map_reduce(ranges, 0, map, |a, b| a + b)
+    }
+
+    fn chunks(&self, ranges: &[Interval]) -> Vec<Interval> {
+        let mut chunks = vec![];
+        for range in ranges {
+            let mut start = range.start;
+            while start < range.end {
+                let end = range.end.min(start.saturating_add(self.chunk_size));
+                chunks.push(Interval::new(start, end));
+                start = end;
+            }
+        }
+        return chunks;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::CancelReason;
+
+    #[test]
+    fn test_chunks() {
+        let executor = Executor::new().with_chunk_size(10);
+        let chunks = executor.chunks(&[Interval::new(0, 25), Interval::new(30, 30), Interval::new(40, 50)]);
+        assert_eq!(vec![
+            Interval::new(0, 10),
+            Interval::new(10, 20),
+            Interval::new(20, 25),
+            Interval::new(40, 50),
+        ], chunks);
+    }
+
+    #[test]
+    fn test_map_reduce() {
+        let ranges = [Interval::with_length(79, 14), Interval::with_length(55, 13), Interval::new(-1000, 1000)];
+        let expected_sum: i64 = ranges.iter().flat_map(|range| range.start..range.end).map(|value| value * 3).sum();
+        for workers in [1, 2, 7] {
+            let executor = Executor::new().with_workers(workers).with_chunk_size(5);
+            assert_eq!(Ok(expected_sum), executor.sum(&ranges, |value| value * 3));
+            let progress = executor.progress();
+            assert_eq!((2027, 2027), (progress.done(), progress.total()));
+
+            assert_eq!(Ok(Some(-2000)), executor.min(&ranges, |value| value * 2));
+            assert_eq!(Ok(Some(92)), executor.map_reduce(&ranges[..2], None, Some, |a, b| a.max(b)));
+            //the progress starts over with every run
+            assert_eq!((27, 27), (progress.done(), progress.total()));
+        }
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let executor = Executor::new();
+        assert_eq!(Ok(None), executor.min(&[], |value| value));
+        assert_eq!(Ok(0), executor.sum(&[Interval::new(5, 5)], |value| value));
+        assert_eq!(0, executor.progress().total());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let progress = Progress::new();
+        let executor = Executor::new().with_workers(1).with_chunk_size(10).with_progress(Arc::clone(&progress));
+        //the first value cancels the run, the worker stops after finishing its chunk
+        let result = executor.sum(&[Interval::new(0, 1000)], |value| {
+            progress.interrupt();
+            value
+        });
+        let cancelled = result.unwrap_err();
+        assert_eq!(CancelReason::Interrupted, cancelled.reason);
+        assert_eq!((10, 1000), (cancelled.done, cancelled.total));
+    }
+
+    #[test]
+    fn test_reports_into_current() {
+        let progress = Progress::new();
+        let sum = progress::run_with(&progress, || Executor::new().sum(&[Interval::new(0, 100)], |value| value));
+        assert_eq!(Ok(4950), sum);
+        assert_eq!((100, 100), (progress.done(), progress.total()));
+    }
+}
@@ -0,0 +1,226 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How far a long running solver is, e.g. seeds checked of all seeds.
+///
+/// The solver reports into the `current` progress, the runner watches it from another thread
+/// and can cancel it. Cancelling only asks the solver to stop: solvers check `is_cancelled`
+/// between steps, the `parallel::Executor` does so before every chunk.
+#[derive(Debug, Default)]
+pub struct Progress {
+    done: AtomicU64,
+    total: AtomicU64,
+    started: Mutex<Option<Instant>>,
+    deadline: Mutex<Option<Instant>>,
+    interrupted: AtomicBool,
+}
+
+impl Progress {
+    pub fn new() -> Arc<Progress> {
+        Arc::new(Progress::default())
+    }
+
+    /// A new run of `total` steps, nothing done yet.
+    pub fn start(&self, total: u64) {
+        self.done.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+        *self.started.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub fn advance(&self, done: u64) {
+        self.done.fetch_add(done, Ordering::Relaxed);
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// 0 until a solver started reporting.
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(self.done() as f64 / total as f64),
+        }
+    }
+
+    /// Time since `start`.
+    pub fn elapsed(&self) -> Duration {
+        self.started.lock().unwrap().map_or(Duration::ZERO, |started| started.elapsed())
+    }
+
+    /// Steps per second since `start`.
+    pub fn throughput(&self) -> Option<f64> {
+        let seconds = self.elapsed().as_secs_f64();
+        if seconds <= 0.0 {
+            return None;
+        }
+        return Some(self.done() as f64 / seconds);
+    }
+
+    /// Estimated time until done, assuming the throughput so far holds.
+    pub fn eta(&self) -> Option<Duration> {
+        let throughput = self.throughput().filter(|throughput| *throughput > 0.0)?;
+        let remaining = self.total().saturating_sub(self.done());
+        return Some(Duration::from_secs_f64(remaining as f64 / throughput));
+    }
+
+    /// Cancels the run once `timeout` passed from now.
+    pub fn set_timeout(&self, timeout: Duration) {
+        *self.deadline.lock().unwrap() = Some(Instant::now() + timeout);
+    }
+
+    /// Cancels the run, e.g. on Ctrl-C.
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_reason().is_some()
+    }
+
+    /// For solver loops: `progress.check()?` stops with the partial state once cancelled.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.cancel_reason() {
+            Some(reason) => Err(Cancelled { reason: reason, done: self.done(), total: self.total() }),
+            None => Ok(()),
+        }
+    }
+
+    fn cancel_reason(&self) -> Option<CancelReason> {
+        if self.interrupted.load(Ordering::Relaxed) {
+            return Some(CancelReason::Interrupted);
+        }
+        match *self.deadline.lock().unwrap() {
+            Some(deadline) if Instant::now() >= deadline => Some(CancelReason::TimedOut),
+            _ => None,
+        }
+    }
+
+    /// One line for the terminal, e.g. `2023 day 5 part 2: 1.2G of 2.5G (48.0%), 310.5M/s, 4s left`.
+    pub fn render(&self, label: &str) -> String {
+        let (done, total) = (self.done(), self.total());
+        let mut line = format!("{}: {} of {}", label, format_count(done as f64), format_count(total as f64));
+        if let Some(fraction) = self.fraction() {
+            line.push_str(&format!(" ({:.1}%)", fraction * 100.0));
+        }
+        if let Some(throughput) = self.throughput() {
+            line.push_str(&format!(", {}/s", format_count(throughput)));
+        }
+        if let Some(eta) = self.eta() {
+            line.push_str(&format!(", {}s left", eta.as_secs()));
+        }
+        return line;
+    }
+}
+
+/// Why a run stopped early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    Interrupted,
+    TimedOut,
+}
+
+/// A cancelled run, with how far it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cancelled {
+    pub reason: CancelReason,
+    pub done: u64,
+    pub total: u64,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.reason {
+            CancelReason::Interrupted => "interrupted",
+            CancelReason::TimedOut => "timed out",
+        };
+        write!(f, "{} after {} of {} steps", reason, self.done, self.total)
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Progress>>> = const { RefCell::new(None) };
+}
+
+/// The progress of the solution running on this thread, see `run_with`.
+/// Outside of a run a fresh one nobody watches.
+pub fn current() -> Arc<Progress> {
+    CURRENT.with(|current| current.borrow().clone())
+        .unwrap_or_default()
+}
+
+/// Runs `f` with `progress` as the `current` one of this thread.
+pub fn run_with<T>(progress: &Arc<Progress>, f: impl FnOnce() -> T) -> T {
+    //puts the previous one back, even if `f` panics
+    struct Restore(Option<Arc<Progress>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(Some(Arc::clone(progress)))));
+    return f();
+}
+
+fn format_count(count: f64) -> String {
+    match count {
+        count if count >= 1e9 => format!("{:.1}G", count / 1e9),
+        count if count >= 1e6 => format!("{:.1}M", count / 1e6),
+        count if count >= 1e3 => format!("{:.1}k", count / 1e3),
+        count => format!("{:.0}", count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress::new();
+        assert_eq!(None, progress.fraction());
+        assert_eq!(None, progress.eta());
+
+        progress.start(2_500_000_000);
+        progress.advance(1_200_000_000);
+        assert_eq!(1_200_000_000, progress.done());
+        assert_eq!(Some(48.0), progress.fraction().map(|fraction| (fraction * 100.0).round()));
+        assert!(progress.render("2023 day 5 part 2").starts_with("2023 day 5 part 2: 1.2G of 2.5G (48.0%)"));
+
+        progress.start(10);
+        assert_eq!(0, progress.done());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let progress = Progress::new();
+        progress.start(10);
+        progress.advance(3);
+        assert_eq!(Ok(()), progress.check());
+
+        progress.set_timeout(Duration::ZERO);
+        assert_eq!(Err(Cancelled { reason: CancelReason::TimedOut, done: 3, total: 10 }), progress.check());
+        progress.interrupt();
+        assert_eq!("interrupted after 3 of 10 steps", progress.check().unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_current() {
+        let progress = Progress::new();
+        run_with(&progress, || current().start(42));
+        assert_eq!(42, progress.total());
+        //outside the run solvers get their own
+        current().start(7);
+        assert_eq!(42, progress.total());
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::progress::{self, Progress};
+use crate::solution::Solution;
+
+//set by the first Ctrl-C, the running solution gets cancelled and the remaining ones skipped
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+//the progress of the running solution, for the Ctrl-C handler
+static RUNNING: Mutex<Option<Arc<Progress>>> = Mutex::new(None);
+
+/// The Ctrl-C handler of `rust_aoc`: cancels the running solution, the remaining ones see `is_interrupted`.
+pub fn interrupt() {
+    //a second Ctrl-C for solutions that don't notice the first one
+    if INTERRUPTED.swap(true, Ordering::Relaxed) {
+        std::process::exit(130);
+    }
+    if let Some(progress) = RUNNING.lock().unwrap().as_ref() {
+        progress.interrupt();
+    }
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` with a fresh `progress::current`, showing how far it is on a terminal.
+///
+/// The progress is cancelled once `timeout` passed or on `interrupt`,
+/// solvers reporting into it stop with how far they got, see `progress::Cancelled`.
+pub fn watched<T>(solution: &dyn Solution, timeout: Option<Duration>, f: impl FnOnce() -> T) -> T {
+    let progress = Progress::new();
+    if let Some(timeout) = timeout {
+        progress.set_timeout(timeout);
+    }
+    *RUNNING.lock().unwrap() = Some(Arc::clone(&progress));
+
+    let label = label(solution);
+    let finished = AtomicBool::new(false);
+    let result = thread::scope(|scope| {
+        let reporter = io::stderr().is_terminal().then(|| scope.spawn(|| {
+            let mut shown = false;
+            while !finished.load(Ordering::Relaxed) {
+                //only solvers reporting into the progress have something to show
+                if progress.total() > 0 {
+                    eprint!("\r{}\x1b[K", progress.render(&label));
+                    shown = true;
+                }
+                thread::park_timeout(Duration::from_millis(250));
+            }
+            if shown {
+                eprint!("\r\x1b[K");
+            }
+        }));
+
+        let result = progress::run_with(&progress, f);
+        finished.store(true, Ordering::Relaxed);
+        if let Some(reporter) = reporter {
+            reporter.thread().unpark();
+        }
+        result
+    });
+
+    *RUNNING.lock().unwrap() = None;
+    result
+}
+
+/// e.g. `2023 day 5 part 2 (brute-force)`
+fn label(solution: &dyn Solution) -> String {
+    let label = format!("{} day {} part {}", solution.year(), solution.day(), solution.part());
+    match solution.variant() {
+        Some(variant) => format!("{} ({})", label, variant),
+        None => label,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measure;
+    use crate::y2023::{day5_2, day7_1};
+
+    #[test]
+    fn test_label() {
+        assert_eq!("2023 day 5 part 2 (brute-force)", label(&day5_2::Solver));
+        assert_eq!("2023 day 7 part 1", label(&day7_1::Solver));
+    }
+
+    #[test]
+    fn test_timeout_stops_the_brute_force() {
+        //ten billion seeds, far more than the brute force gets through before the timeout
+        let input = "seeds: 0 10000000000\n\
+                     \n\
+                     seed-to-location map:\n\
+                     7 0 1";
+        let measurement = watched(&day5_2::Solver, Some(Duration::from_millis(100)), || measure::measure(&day5_2::Solver, input));
+
+        let error = measurement.answer.unwrap_err();
+        let done = error.strip_prefix("cancelled, timed out after ")
+            .and_then(|rest| rest.strip_suffix(" of 10000000000 steps"))
+            .and_then(|done| done.parse::<u64>().ok())
+            .unwrap_or_else(|| panic!("unexpected error: {}", error));
+        assert!(done < 10_000_000_000, "{}", error);
+        //the next one starts over without a timeout
+        assert_eq!("7", watched(&day5_2::Solver, None, || day5_2::solve("seeds: 0 1\n\nseed-to-location map:\n7 0 1")).unwrap().to_string());
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use crate::error::Error;
+use crate::y2023;
+use crate::y2024;
+
+/// One part of one puzzle day, e.g. 2023 day 7 part 2.
+///
+/// Every `dayX_Y` module exposes its part as `pub fn solve(&str) -> Result<Answer, Error>`
+/// and a unit struct `Solver` implementing this trait on top of it,
+/// which is listed in its year's `register!`.
+/// `solve` calls `measure::parsed` once its input is parsed, so the runner can time parsing on its own.
+pub trait Solution {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn part(&self) -> u8;
+    /// Names another way to solve a part that already has a solution, e.g. a brute force to compare with.
+    /// Variants only run when selected by name.
+    fn variant(&self) -> Option<&'static str> {
+        None
+    }
+    fn solve(&self, input: &str) -> Result<Answer, Error>;
+}
+
+/// The result of a puzzle part.
+/// Nearly all answers are numbers, but some puzzles expect a word or code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(number: i32) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(number: i64) -> Self {
+        Answer::Number(number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Answer::Number(number as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+/// Defines a year's `solutions()` from the listed `dayX_Y` modules and
+/// generates a test per module, which runs its `Solver` on the example
+/// (see `crate::examples`), so no registered solution goes without one.
+macro_rules! register {
+    ($($module:ident),* $(,)?) => {
+        pub fn solutions() -> Vec<&'static dyn $crate::solution::Solution> {
+            vec![$(&$module::Solver),*]
+        }
+
+        #[cfg(test)]
+        mod example_tests {
+            $(
+                #[test]
+                fn $module() {
+                    if let Err(e) = $crate::examples::check(&super::$module::Solver) {
+                        panic!("{}", e);
+                    }
+                }
+            )*
+        }
+    };
+}
+pub(crate) use register;
+
+/// All registered solutions, ordered by year, day and part, variants after the solution of their part.
+pub fn all() -> Vec<&'static dyn Solution> {
+    let mut solutions = y2023::solutions();
+    solutions.extend(y2024::solutions());
+    solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_sorted_and_unique() {
+        let keys: Vec<(u16, u8, u8, Option<&str>)> = all().into_iter()
+            .map(|solution| (solution.year(), solution.day(), solution.part(), solution.variant()))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, keys);
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("42", Answer::from(42usize).to_string());
+        assert_eq!("-3", Answer::from(-3i32).to_string());
+        assert_eq!("abc", Answer::from("abc".to_string()).to_string());
+    }
+}
@@ -0,0 +1,57 @@
+/// How the cells of a column are padded to its width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Renders the rows below a header line, every column as wide as its widest cell.
+///
+/// Adjacent right aligned columns (the numbers of `2023   7    1`) are separated
+/// by one space, all other columns by two. Trailing spaces are trimmed.
+pub fn render(columns: &[(&str, Align)], rows: &[Vec<String>]) -> String {
+    let header: Vec<String> = columns.iter().map(|(title, _)| title.to_string()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|title| title.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let mut line = String::new();
+        for (idx, cell) in row.iter().enumerate() {
+            let (_, align) = columns[idx];
+            if idx > 0 {
+                let packed = align == Align::Right && columns[idx - 1].1 == Align::Right;
+                line.push_str(if packed { " " } else { "  " });
+            }
+            match align {
+                Align::Left => line.push_str(&format!("{:<width$}", cell, width = widths[idx])),
+                Align::Right => line.push_str(&format!("{:>width$}", cell, width = widths[idx])),
+            }
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let columns = [("day", Align::Right), ("part", Align::Right), ("answer", Align::Left), ("time", Align::Right)];
+        let rows = vec![
+            vec!["7".to_string(), "1".to_string(), "6440".to_string(), "1.5µs".to_string()],
+            vec!["12".to_string(), "2".to_string(), "-".to_string(), "12ms".to_string()],
+        ];
+        let expected = "day part  answer   time\n  \
+                          7    1  6440    1.5µs\n \
+                         12    2  -        12ms\n";
+        assert_eq!(expected, render(&columns, &rows));
+    }
+}
@@ -0,0 +1,61 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// xorshift64, reproducible random numbers for tests and benches without a rand dependency.
+///
+/// Good enough to spread test cases, not for anything needing real randomness.
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// `seed` must not be 0, xorshift would stay at 0 forever.
+    pub fn new(seed: u64) -> XorShift {
+        assert_ne!(0, seed, "xorshift needs a seed other than 0");
+        XorShift { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return self.state;
+    }
+
+    /// The next number in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+/// A fresh directory below the system's temp dir holding `files`, given as relative path and content,
+/// e.g. an input directory, as the real inputs aren't part of the repository.
+pub fn temp_dir_with(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = env::temp_dir().join(format!("rust_aoc_{}_{}", name, std::process::id()));
+    if root.exists() {
+        fs::remove_dir_all(&root).expect("temp dir can be cleared");
+    }
+    for (path, content) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().expect("files are in the dir")).expect("temp dir can be created");
+        fs::write(&path, content).expect("temp file can be written");
+    }
+    return root;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let numbers: Vec<u64> = (0..1000).scan(XorShift::new(42), |random, _| Some(random.below(13))).collect();
+        let again: Vec<u64> = (0..1000).scan(XorShift::new(42), |random, _| Some(random.below(13))).collect();
+        assert_eq!(numbers, again);
+        assert!(numbers.iter().all(|number| *number < 13));
+        //every value shows up
+        assert!((0..13).all(|value| numbers.contains(&value)));
+    }
+}
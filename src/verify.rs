@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::solution::{Answer, Solution};
+use crate::table::{self, Align};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The solver reproduced the accepted answer.
+    Pass,
+    /// Wrong answer, or no answer at all because input or solver failed.
+    Fail,
+    /// The solver answered, but there is no accepted answer to compare with.
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Unknown => "unknown",
+        };
+        f.pad(name)
+    }
+}
+
+/// Outcome of one solution compared against its accepted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The answer, or why there is none.
+    pub actual: Result<Answer, String>,
+    pub expected: Option<Answer>,
+}
+
+impl Check {
+    /// `actual` is the answer of `solution`, or the input or solver error as text.
+    pub fn new(solution: &dyn Solution, actual: Result<Answer, String>, expected: Option<&Answer>) -> Check {
+        Check {
+            year: solution.year(),
+            day: solution.day(),
+            part: solution.part(),
+            actual: actual,
+            expected: expected.cloned(),
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (Err(_), _) => Status::Fail,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+/// One row per check plus a summary line, e.g.
+///
+/// ```text
+/// year day part  status  answer  expected
+/// 2023   7    1  pass    6440    6440
+/// 2023   7    2  FAIL    5904    5905
+/// ```
+pub fn format_table(checks: &[Check]) -> String {
+    let columns = [
+        ("year", Align::Right),
+        ("day", Align::Right),
+        ("part", Align::Right),
+        ("status", Align::Left),
+        ("answer", Align::Left),
+        ("expected", Align::Left),
+    ];
+    let rows: Vec<Vec<String>> = checks.iter()
+        .map(|check| vec![
+            check.year.to_string(),
+            check.day.to_string(),
+            check.part.to_string(),
+            check.status().to_string(),
+            match &check.actual {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e),
+            },
+            check.expected.as_ref().map_or(String::from("-"), |expected| expected.to_string()),
+        ])
+        .collect();
+
+    let mut table = table::render(&columns, &rows);
+    let count = |status: Status| checks.iter().filter(|check| check.status() == status).count();
+    table.push_str(&format!("{} passed, {} failed, {} unknown",
+        count(Status::Pass), count(Status::Fail), count(Status::Unknown)));
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(day: u8, actual: Result<Answer, String>, expected: Option<i64>) -> Check {
+        Check { year: 2023, day: day, part: 1, actual: actual, expected: expected.map(Answer::Number) }
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::Pass, check(1, Ok(Answer::Number(3)), Some(3)).status());
+        assert_eq!(Status::Fail, check(1, Ok(Answer::Number(4)), Some(3)).status());
+        assert_eq!(Status::Unknown, check(1, Ok(Answer::Number(4)), None).status());
+        assert_eq!(Status::Fail, check(1, Err("input file not found".to_string()), None).status());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let checks = vec![
+            check(7, Ok(Answer::Number(6440)), Some(6440)),
+            check(12, Ok(Answer::Number(5)), Some(6)),
+            check(13, Err("no solution".to_string()), None),
+        ];
+        let expected = "year day part  status  answer              expected\n\
+                        2023   7    1  pass    6440                6440\n\
+                        2023  12    1  FAIL    5                   6\n\
+                        2023  13    1  FAIL    error: no solution  -\n\
+                        1 passed, 2 failed, 0 unknown";
+        assert_eq!(expected, format_table(&checks));
+    }
+}
@@ -1,30 +1,37 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
 
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day1.1.txt")?;
-    let reader = BufReader::new(file);
+pub struct Solver;
 
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 1 }
+    fn part(&self) -> u8 { 1 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
     let mut sum = 0;
-    for maybe_line in reader.lines() {
-        let line = maybe_line?;
+    for (idx, line) in input.lines().enumerate() {
         //parse first number
-        let first_number = findFirstNumber(line.chars());
-        let last_number = findFirstNumber(line.chars().rev());
+        let first_number = find_first_number(line.chars()).map_err(|e| e.in_line(idx + 1))?;
+        let last_number = find_first_number(line.chars().rev()).map_err(|e| e.in_line(idx + 1))?;
         let combined_number = first_number * 10 + last_number;
         sum += combined_number;
     }
-    println!("{}", sum);
 
-    Ok(())
+    Ok(Answer::from(sum))
 }
 
-fn findFirstNumber(input: impl Iterator<Item=char>) -> i32 {
+fn find_first_number(input: impl Iterator<Item=char>) -> Result<i32, ParseError> {
     for char in input {
-        if char >= '0' && char <= '9' {
-            return char as i32 - '0' as i32;
+        if char.is_ascii_digit() {
+            return Ok(char as i32 - '0' as i32);
         }
     }
 
-    panic!("No number found in input");
-}
\ No newline at end of file
+    Err(ParseError::new("No number found in line"))
+}
@@ -1,43 +1,50 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use crate::error::{Error, ParseError};
+use crate::solution::{Answer, Solution};
 
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day1.2.txt")?;
-    let reader = BufReader::new(file);
+pub struct Solver;
 
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 1 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
     let mut sum = 0;
-    for maybe_line in reader.lines() {
-        let line = maybe_line?;
+    for (idx, line) in input.lines().enumerate() {
         //parse first number
-        let first_number = find_first_number(&line);
-        let last_number = find_last_number(&line);
+        let first_number = find_first_number(line).map_err(|e| e.in_line(idx + 1))?;
+        let last_number = find_last_number(line).map_err(|e| e.in_line(idx + 1))?;
         // println!("{} {}", first_number, last_number);
         let combined_number = first_number * 10 + last_number;
         sum += combined_number;
     }
-    println!("{}", sum);
 
-    Ok(())
+    Ok(Answer::from(sum))
 }
 
-fn find_first_number(input: &str) -> i32 {
+fn find_first_number(input: &str) -> Result<i32, ParseError> {
     return find_number(input, |iteration_range| iteration_range);
 }
-fn find_last_number(input: &str) -> i32 {
+fn find_last_number(input: &str) -> Result<i32, ParseError> {
     return find_number(input, |iteration_range| Box::from(iteration_range.rev()));
 }
 
 const NUMBER_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
 
-fn find_number<F>(input: &str, iterator_modifier: F) -> i32 
+fn find_number<F>(input: &str, iterator_modifier: F) -> Result<i32, ParseError>
     where F: Fn(Box<dyn DoubleEndedIterator<Item=usize>>) -> Box<dyn DoubleEndedIterator<Item=usize>> {
     let char_array = input.chars().collect::<Vec<char>>();
 
     for i in iterator_modifier(Box::from(0..char_array.len())) {
         let char = char_array[i];
 
-        if char >= '0' && char <= '9' {
-            return (char as i32) - ('0' as i32);
+        if char.is_ascii_digit() {
+            return Ok((char as i32) - ('0' as i32));
         } 
 
         'number_loop: for number_name in NUMBER_NAMES.iter().enumerate() {
@@ -54,11 +61,11 @@ fn find_number<F>(input: &str, iterator_modifier: F) -> i32
                 num_index += 1;
             }
             //enumeration starts at 0, therefor add 1
-            return num_num as i32 + 1;
+            return Ok(num_num as i32 + 1);
         }
     }
 
-    panic!("No number found in input");
+    Err(ParseError::new("No number found in line"))
 }
 
 #[cfg(test)]
@@ -66,31 +73,31 @@ mod tests {
     #[test]
     fn test_find_number() {
         let input = "abcone2threexyz";
-        let result = super::find_first_number(input);
+        let result = super::find_first_number(input).unwrap();
         assert_eq!(result, 1);
     }
     #[test]
     fn test_find_number2() {
         let input = "abconxe2threexyz";
-        let result = super::find_first_number(input);
+        let result = super::find_first_number(input).unwrap();
         assert_eq!(result, 2);
     }
     #[test]
     fn test_find_number3() {
         let input = "abconxethreexyz";
-        let result = super::find_first_number(input);
+        let result = super::find_first_number(input).unwrap();
         assert_eq!(result, 3);
     }
     #[test]
     fn test_find_last_number1() {
         let input = "1234";
-        let result = super::find_last_number(input);
+        let result = super::find_last_number(input).unwrap();
         assert_eq!(result, 4);
     }
     #[test]
     fn test_find_last_number2() {
         let input = "124three";
-        let result = super::find_last_number(input);
+        let result = super::find_last_number(input).unwrap();
         assert_eq!(result, 3);
     }
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::error::{parse_lines, parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug)]
 pub struct Game {
@@ -14,67 +17,81 @@ pub struct SetOfCube {
     pub blue: u32,
 }
 
-impl From<&str> for Game {
-    fn from(line: &str) -> Self {
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
         //example input:
         //Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
         if let Some((game_id_str, sets)) = line.split_once(':') {
-            let game_id = match game_id_str[5..].parse::<u32>() {
-                Ok(id) => id,
-                Err(_) => panic!("{}", format!("Could not parse number! {game_id_str} starting at index 5"))
-            };
-            let sets_of_cubes = sets.split(';').map(|set| SetOfCube::from(set)).collect();
-            return Game {
+            let id_str = game_id_str.strip_prefix("Game ")
+                .ok_or_else(|| ParseError::at("Line should start with 'Game '", line, game_id_str))?;
+            let game_id = parse_number::<u32>(line, id_str)?;
+            let sets_of_cubes = sets.split(';')
+                .map(|set| set.parse::<SetOfCube>().map_err(|e| e.within(line, set)))
+                .collect::<Result<Vec<SetOfCube>, ParseError>>()?;
+            return Ok(Game {
                 id: game_id,
                 sets_of_cubes: sets_of_cubes
-            };
+            });
         } else {
-            panic!("{}", format!("Invalid input! {line}"));
+            return Err(ParseError::new(format!("Invalid input, missing ':' in '{line}'")));
         }
     }
 }
 
-impl From<&str> for SetOfCube {
+impl FromStr for SetOfCube {
+    type Err = ParseError;
+
     //example input:
     //3 blue, 4 red
-    fn from(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         //map each color to its count
         let count_per_color: HashMap<&str, u32> = input.split(',')
             .map(|count_color| {
                 let (count, color) = match count_color.trim().split_once(' ') {
                     Some((count, color)) => (count, color),
-                    None => panic!("{}", format!("Could not parse color: {count_color}"))
+                    None => return Err(ParseError::at(format!("Could not parse color: {count_color}"), input, count_color))
                 };
+                if !["red", "green", "blue"].contains(&color) {
+                    return Err(ParseError::at(format!("Unknown color '{color}'"), input, color));
+                }
                 //parse the u32
-                let count = match count.parse::<u32>() {
-                    Ok(count) => count,
-                    Err(_) => panic!("{}", format!("Could not parse number! {count}"))
-                };
-                return (color, count);
-        }).collect(); //collect as a map
+                let count = parse_number::<u32>(input, count)?;
+                return Ok((color, count));
+        }).collect::<Result<HashMap<&str, u32>, ParseError>>()?; //collect as a map
         
         // println!("{:?}", count_per_color);
-        SetOfCube { 
+        Ok(SetOfCube { 
             red: *count_per_color.get("red").unwrap_or(&0),
             green: *count_per_color.get("green").unwrap_or(&0),
             blue: *count_per_color.get("blue").unwrap_or(&0),
-        }
+        })
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 2 }
+    fn part(&self) -> u8 { 1 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
     }
 }
 
-fn _main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day2.1.txt")?;
-    let reader = BufReader::new(file);
+pub fn solve(input: &str) -> Result<Answer, Error> {
     //12 red cubes, 13 green cubes, and 14 blue cubes
     let max_red_cubes = 12;
     let max_green_cubes = 13;
     let max_blue_cubes = 14;
     //add up the IDs of the games that would have been possible, you get 8.
     let mut sum = 0;
-    'next_game: for maybe_line in reader.lines() {
-        let line = maybe_line?;
-        let game = Game::from(&*line);
-
+    let games: Vec<Game> = parse_lines(input, Game::from_str)?;
+    measure::parsed();
+    'next_game: for game in games {
         for set in game.sets_of_cubes {
             if (set.red > max_red_cubes) 
                 || (set.green > max_green_cubes) 
@@ -85,9 +102,8 @@ fn _main() -> std::io::Result<()> {
         
         sum += game.id;
     }
-    println!("{}", sum);
 
-    Ok(())
+    Ok(Answer::from(sum))
 }
 
 #[cfg(test)]
@@ -97,7 +113,7 @@ mod tests {
     #[test]
     fn test_create_game() {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game = Game::from(input);
+        let game = Game::from_str(input).unwrap();
         assert_eq!(game.id, 1);
         assert_eq!(game.sets_of_cubes[0], SetOfCube { red: 4, green: 0, blue: 3 });
         assert_eq!(game.sets_of_cubes[1], SetOfCube { red: 1, green: 2, blue: 6 });
@@ -106,7 +122,16 @@ mod tests {
     #[test]
     fn test_from_setofcubes() {
         let input = "3 blue, 4 red, 1 green";
-        let set_of_cubes = SetOfCube::from(input);
+        let set_of_cubes = SetOfCube::from_str(input).unwrap();
         assert_eq!(set_of_cubes, SetOfCube { red: 4, green: 1, blue: 3 });
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_invalid_game() {
+        let error = Game::from_str("Game 1: 3 blue, 4 rot; 2 green").unwrap_err();
+        assert_eq!("column 19: Unknown color 'rot'", error.to_string());
+        let error = Game::from_str("Game 1: 3 blue; x green").unwrap_err();
+        assert_eq!("column 17: 'x' is not a valid number", error.to_string());
+        assert!(Game::from_str("Game one: 3 blue").is_err());
+        assert!(Game::from_str("3 blue").is_err());
+    }
+}
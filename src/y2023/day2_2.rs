@@ -1,17 +1,28 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::str::FromStr;
 
+use crate::error::{parse_lines, Error};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 use crate::y2023::day2_1::Game;
 
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day2.2.txt")?;
-    let reader = BufReader::new(file);
+pub struct Solver;
 
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 2 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
     //add up the IDs of the games that would have been possible, you get 8.
     let mut sum_of_game_powers = 0;
-    for maybe_line in reader.lines() {
-        let line = maybe_line?;
-        let game = Game::from(&*line);
+    let games: Vec<Game> = parse_lines(input, Game::from_str)?;
+    measure::parsed();
+    for game in games {
 
         //find the maximum number for each color,
         //as this is the number required to play the game, 
@@ -29,7 +40,6 @@ fn main() -> std::io::Result<()> {
         let game_power = min_red * min_green * min_blue;
         sum_of_game_powers += game_power;
     }
-    println!("{}", sum_of_game_powers);
 
-    Ok(())
+    Ok(Answer::from(sum_of_game_powers))
 }
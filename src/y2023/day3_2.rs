@@ -1,17 +1,23 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use std::collections::BTreeMap;
-use std::iter::Peekable;
-use std::io::{self};
-use std::iter::Enumerate;
-use std::str::Chars;
 use std::fmt;
-use std::cmp;
 
-fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "debug");
-    env_logger::init();
+use crate::error::{Error, ParseError};
+use crate::grid::{Connectivity, Grid, Region, Regions};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 3 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
     // The missing part wasn't the only issue - one of the gears in the engine is wrong. 
     // A gear is any * symbol that is adjacent to exactly two part numbers. 
     // Its gear ratio is the result of multiplying those two numbers together.
@@ -37,175 +43,63 @@ fn main() -> std::io::Result<()> {
     // (The * adjacent to 617 is not a gear because it is only adjacent to one part number.) 
     // Adding up all of the gear ratios produces 467835.
 
-    let file = File::open("res/y2023/day3_1.txt")?;
-    let reader = BufReader::new(file);
-    let mut grid = Grid::new();
-
-    fill_map_from_text(Box::new(reader.lines()), &mut grid)?;
-    let gear_ratio_sum = sum_gear_ratios(grid);
+    let schematic = parse_schematic(input)?;
+    measure::parsed();
+    let gear_ratio_sum = sum_gear_ratios(schematic)?;
 
-    println!("Sum of gear ratios: {}", gear_ratio_sum);
-    Ok(())
+    Ok(Answer::from(gear_ratio_sum))
 }
 
-fn sum_gear_ratios(grid: Grid) -> i32 {
+fn sum_gear_ratios(schematic: Schematic) -> Result<i32, ParseError> {
     let mut sum = 0;
     //for each gear token 
-    let tokens = grid.tokens.iter().filter(|(_, token)| token.token_type == TokenType::Gear);
-    for (point, token) in tokens {
-        log::trace!("sum gear ratios: {} {}", point, token);
-        if token.value.len() != 1 {
-            panic!("Gear should always be '*', but was: {}", token.value);
+    let gears = schematic.tokens.iter().filter(|(_, token_type, _)| **token_type == TokenType::Gear);
+    for (id, _, region) in gears {
+        log::trace!("sum gear ratios: {} {}", region.start(), schematic.text(region));
+        if region.len() != 1 {
+            return Err(region.start().parse_error(format!("Gear should always be '*', but was '{}'", schematic.text(region))));
         }
 
-        let surrounding_nums: Vec<i32> = grid.find_surroundings(point, token)
-            .into_iter()
-            .filter(|(_, t)| t.token_type == TokenType::Numeric)
-            .map(|(_, t)| t.value.parse::<i32>()
-                .expect(&["Could not parse token value: ", &t.value].join(" ")))
-            .collect();
+        let surrounding_nums: Vec<i32> = schematic.tokens.touching(id, Connectivity::Eight)
+            .filter(|other| *schematic.tokens.label(*other) == TokenType::Numeric)
+            .map(|other| schematic.part_number(schematic.tokens.region(other)))
+            .collect::<Result<Vec<i32>, ParseError>>()?;
         if surrounding_nums.len() == 2 {
             //it's a gear! sum it!
-            let num_sum = surrounding_nums.iter().fold(1, |acc, num| acc * num);
+            let num_sum = surrounding_nums.iter().product::<i32>();
             log::debug!("surroundings summed: {}", num_sum);
             sum += num_sum;
         }
 
     }
-    sum
+    Ok(sum)
 }
 
-fn fill_map_from_text(iterator: Box<dyn Iterator<Item=Result<String, std::io::Error>>>, grid: &mut Grid) -> Result<(), io::Error> {
-    for (y, line) in iterator.enumerate() {
-        let line: String = match line {
-            Ok(line) => line,
-            Err(e) => panic!("Error reading line {}", e)
-        };
-        let mut char_iter: Peekable<Enumerate<Chars>> = line.chars().enumerate().peekable();
-        
-        while let Some((x, char)) = char_iter.peek() {
-            let x = *x;
-            let char = *char;
-            if char == '.' {
-                //skip dots
-                char_iter.next(); 
-                grid.put_char(y, x, char);
-                continue;
-            }
-
-            let token_type = TokenType::from(char);
-            let mut token = Token {
-                token_type: token_type,
-                value: String::new()
-            };
-            while let Some((_, char)) = char_iter.peek() {
-                let char = *char;
-                if TokenType::from(char) == token.token_type {
-                    let (x, next_char) = char_iter.next().expect("next should be present due to peek returning Some");
-                    assert_eq!(char, next_char, "next should = peek");
-                    grid.put_char(y, x, char);
-                    token.value.push(char);
-                } else {
-                    break;
-                }
-            }
-            grid.put_token(y, x, token);
-        }
-    }
-    Ok(())
+fn parse_schematic(text: &str) -> Result<Schematic, ParseError> {
+    let chars: Grid<char> = text.parse()?;
+    //dots separate the tokens, every other char belongs to a run of its type
+    let tokens = chars.regions(Connectivity::Horizontal, |char| match TokenType::from(*char) {
+        TokenType::Dot => None,
+        token_type => Some(token_type),
+    });
+    Ok(Schematic { chars: chars, tokens: tokens })
 }
 
-struct Grid {
-    internal_map: Vec<Vec<char>>,
-    tokens: BTreeMap<Point, Token>
+/// The engine schematic as chars, plus the tokens found in it.
+struct Schematic {
+    chars: Grid<char>,
+    tokens: Regions<TokenType>
 }
 
-impl Grid {
-    fn put_char(&mut self, y: usize, x: usize, char: char) {
-        log::trace!("put_char: {} {} {}", y, x, char);
-        if self.internal_map.len() <= y {
-            //need to resize
-            self.internal_map.resize(y+1, vec![' '])
-        }
-        self.internal_map[y].insert(x, char);
-    }
-    fn put_token(&mut self, y: usize, x: usize, token: Token) {
-        let point = Point::new(y, x);
-        if let Some(old_value) = &self.tokens.insert(point, token) {
-            panic!("Duplicate value in map, should not happen! {:?}", old_value);
-        }
-    }
-
-    fn new() -> Self {
-        Grid {internal_map: vec![vec![' ']], tokens: BTreeMap::new()}
+impl Schematic {
+    fn text(&self, region: &Region) -> String {
+        region.values(&self.chars).collect()
     }
 
-    fn find_surroundings(&self, point: &Point, token: &Token) -> Vec<(Point, &Token)> {
-        let mut result: Vec<(Point, &Token)> = vec![];
-        //calculate the from and to Points:
-        let token_len = token.value.len();
-        let (from_top_left, to_bottom_right) = point.surrounding_range(token_len);
-        //scan the area for non '.'
-        log::debug!("surrounding area points: {} {}", from_top_left, to_bottom_right);
-
-        for y in from_top_left.y..=to_bottom_right.y {
-            let mut skip_chars = 0;
-            for x in from_top_left.x..=to_bottom_right.x {
-                if skip_chars > 0 {
-                    skip_chars -= 1;
-                    continue;
-                }
-                log::debug!("{} {}", y, x);
-                //skip the searched tokens range (hope i can just x += len :-)
-                if point.x == x && point.y == y {
-                    //skip the length of the token, to not double track it. subtract 1, as we're past this token anyway
-                    skip_chars += token_len - 1;
-                    continue;
-                }
-                let char = *&self.internal_map[y][x];
-                if char == '.' {
-                    //skip dots
-                    continue;
-                }
-                //we found a non '.', find the start index of the token
-                let char_pos = Point::new(y, x);
-                let token_start: Point = self.find_token_start(char_pos);
-                if let Some(token) = self.tokens.get(&token_start) {
-                    //skip the token, e.g. all chars from start to end
-                    let token_end_x = token_start.x + token.value.len();
-                    skip_chars += token_end_x - x;
-                    result.push((token_start, token));
-                } else {
-                    panic!("Could not find a token that should be there!\n\
-                              searching adjacents to token: {}, pos: {} \n. \
-                              found adjacent char {}, pos: {},\n. \
-                              calculated token start: {}", 
-                              token, point,
-                              char, char_pos,
-                              token_start
-                            );
-                }
-            }
-        }
-        result
-    }
-
-    fn find_token_start(&self, current_idx: Point) -> Point {
-        //just walk leftwards from the start index, until you find a non matching token char
-        let mut token_start_x: usize = current_idx.x;
-        let token_type = TokenType::from(self.internal_map[current_idx.y][current_idx.x]);
-        loop {
-            if token_start_x == 0 {
-                break; //break due to @start of line
-            }
-            let previous_char = self.internal_map[current_idx.y][token_start_x - 1];
-            if token_type != TokenType::from(previous_char) {
-                break; //found not matching token
-            }
-            token_start_x -= 1;
-        }
-
-        Point::new(current_idx.y, token_start_x)
+    fn part_number(&self, region: &Region) -> Result<i32, ParseError> {
+        let value = self.text(region);
+        value.parse()
+            .map_err(|_| region.start().parse_error(format!("'{}' is not a valid part number", value)))
     }
 }
 
@@ -244,207 +138,110 @@ impl From<char> for TokenType {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Token {
-    token_type: TokenType,
-    value: String
-}
-impl fmt::Display for Token {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}: {}", self.token_type, self.value)
-    }
-}
-
-impl Token {
-    fn new(value: String) -> Self {
-        if let Some(char) = value.chars().next() {
-            Token {token_type: TokenType::from(char), value: value}
-        } else {
-            //empty string
-            panic!("Can't construct a Token out of an empty String! {}", value);
-        }
-    }
-}
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
-struct Point {
-    y: usize,
-    x: usize
-}
-impl fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "y:{} x:{}", self.y, self.x)
-    }
-}
-
-impl Point {
-    fn new(y: usize, x: usize) -> Self {
-        Point {y: y, x: x}
-    }
-
-    fn surrounding_range(&self, length: usize) -> (Point, Point) {
-        (
-            (Point::new(
-                cmp::max(self.y, 1) - 1, 
-                cmp::max(self.x, 1) - 1)), 
-            (Point::new(self.y + 1, self.x + length)) //0 based!
-        )
-    }
-}
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::ops::Bound::Included;
+    use crate::examples;
+    use crate::grid::Point;
+    use std::fs;
+    use std::io;
+
+    /// "start type: text" of each token, e.g. "y:0 x:0 Numeric: 467"
+    fn describe(schematic: &Schematic, ids: impl Iterator<Item = usize>) -> Vec<String> {
+        ids.map(|id| {
+                let region = schematic.tokens.region(id);
+                format!("{} {}: {}", region.start(), schematic.tokens.label(id), schematic.text(region))
+            })
+            .collect()
+    }
 
     #[test]
-    fn test_grid() {
-        Grid::new();
+    fn test_parse_schematic_keeps_chars() {
+        let input = examples::load(2023, 3, 2).unwrap();
+        let schematic = parse_schematic(&input).unwrap();
+        assert_eq!(input.trim_end(), schematic.chars.to_string());
+        assert_eq!(Some(&'*'), schematic.chars.get(Point::new(1, 3)));
     }
 
     #[test]
-    fn test_fill_map_from_text() -> Result<(), io::Error> {
-        let file = File::open("res/y2023/day3.test.txt")?;
-        let reader = BufReader::new(file);
-        let mut grid = Grid::new();
-        fill_map_from_text(Box::new(reader.lines()), &mut grid)?;
-    
-        let mut expected_map: BTreeMap<Point, Token> = BTreeMap::new();
+    fn test_parse_schematic_tokens() -> Result<(), io::Error> {
+        let text = fs::read_to_string("res/y2023/day3.test.txt")?;
+        let schematic = parse_schematic(&text).unwrap();
+
         //inserting the following map:
         // 467..114..
         // ...*......
         // ..35..633.
         // ...abc#...
-        expected_map.insert(Point::new(0, 0), Token::new("467".to_string()));
-        expected_map.insert(Point::new(0, 5), Token::new("114".to_string()));
-        expected_map.insert(Point::new(1, 3), Token::new("*".to_string()));
-        expected_map.insert(Point::new(2, 2), Token::new("35".to_string()));
-        expected_map.insert(Point::new(2, 6), Token::new("633".to_string()));
-        
         //This follows the design decision to combine adjacent symbols:
-        expected_map.insert(Point::new(3, 3), Token::new("abc#".to_string()));
-
-        assert_eq!(expected_map, grid.tokens);
+        let expected = vec![
+            "y:0 x:0 Numeric: 467",
+            "y:0 x:5 Numeric: 114",
+            "y:1 x:3 Gear: *",
+            "y:2 x:2 Numeric: 35",
+            "y:2 x:6 Numeric: 633",
+            "y:3 x:3 Symbol: abc#",
+        ];
+        assert_eq!(expected, describe(&schematic, 0..schematic.tokens.len()));
         Ok(())
     }
 
     #[test]
-    fn test_sum_gear_ratios() -> Result<(), io::Error>  {
-        //inserting the following map:
-        let input = "467..114..\n\
-                    ...*......\n\
-                    ..35..633.\n\
-                    ......#...\n\
-                    617*......\n\
-                    .....+.58.\n\
-                    ..592.....\n\
-                    ......755.\n\
-                    ...$.*....\n\
-                    .664.598..";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-        let mut grid = Grid::new();
-        fill_map_from_text(lines, &mut grid)?;
-        //expected sum:
-        //
-        let sum = sum_gear_ratios(grid);
+    fn test_sum_gear_ratios() {
+        let input = examples::load(2023, 3, 2).unwrap();
+        let schematic = parse_schematic(&input).unwrap();
+        let sum = sum_gear_ratios(schematic).unwrap();
         assert_eq!(467 * 35 + 755 * 598, sum);
-        Ok(())
     }
 
     #[test]
-    fn test_map_range() {
-        let mut map: BTreeMap<Point, Token> = BTreeMap::new();
-        //inserting the following map:
-        // 467..114..
-        // ...*......
-        // ..35..633.
-        // ...abc#...
-        let interesting_point = Point::new(2, 2);
+    fn test_double_gear_is_an_error() {
+        let schematic = parse_schematic("1**2").unwrap();
+        let error = sum_gear_ratios(schematic).unwrap_err();
+        assert_eq!("line 1, column 2: Gear should always be '*', but was '**'", error.to_string());
+    }
 
-        map.insert(Point::new(0, 0), Token::new("467".to_string()));
-        map.insert(Point::new(0, 5), Token::new("114".to_string()));
-        map.insert(Point::new(1, 3), Token::new("*".to_string()));
-        map.insert(interesting_point, Token::new("35".to_string()));
-        map.insert(Point::new(2, 6), Token::new("633".to_string()));
-        map.insert(Point::new(3, 3), Token::new("abc#".to_string()));
+    #[test]
+    fn test_touching() -> Result<(), io::Error> {
+        let text = fs::read_to_string("res/y2023/day3.test.txt")?;
+        let schematic = parse_schematic(&text).unwrap();
 
         //now we want all elements surrounding "35"
-        let surrounding_range: (Point, Point) = interesting_point.surrounding_range(2);
-        assert_eq!((Point::new(1, 1)), surrounding_range.0);
-        assert_eq!((Point::new(3, 4)), surrounding_range.1);
-
-        let map_range: BTreeMap<Point, String> = map.range_mut((Included(surrounding_range.0), Included(surrounding_range.1)))
-            .map(|(ptr, token)| (*ptr, format!("{} {}", token.value, token.token_type)))
-            //can't use token directly, as I would have to copy it, as it's shared in the map and can't be derefed
-            //I don't like that I have to implement the Copy Trait for Token here,
-            //as I think the *token should be able to be consumered here to be put into the new map
-            //doesn't work, as the range iterator doesn't allow me to remove elements from the underlying map 
-            .fold(BTreeMap::new(), |mut map, (k, v)| {
-                map.insert(k, v);
-                return map;
-            });
-
-        assert_eq!("35 Numeric".to_string(), *map_range.get(&Point::new(2, 2)).unwrap());
-        assert_eq!("* Gear".to_string(), *map_range.get(&Point::new(1, 3)).unwrap());
-        assert_eq!("abc# Symbol".to_string(), *map_range.get(&Point::new(3, 3)).unwrap());
+        let id = schematic.tokens.id_at(Point::new(2, 2)).unwrap();
+        assert_eq!(vec!["y:1 x:3 Gear: *", "y:3 x:3 Symbol: abc#"],
+            describe(&schematic, schematic.tokens.touching(id, Connectivity::Eight)));
+        Ok(())
     }
 
     #[test]
-    fn test_map_range_sophisticated() -> Result<(), io::Error> {
+    fn test_touching_sophisticated() {
         let input = "x.x.x.x\n\
                      .x.x.x.\n\
                      x.123.x\n\
                      .x.x.x.\n\
                      x.x.x.x";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-        let mut grid = Grid::new();
-        fill_map_from_text(lines, &mut grid)?;
-
-        let interesting_point = Point::new(2, 2);
-        let token = grid.tokens.get(&interesting_point).unwrap();
-        let surrounding_symbols: Vec<(Point, &Token)> = grid.find_surroundings(&interesting_point, token)
-            .into_iter()
-            .filter(|(_, t)| t.token_type == TokenType::Symbol || t.token_type == TokenType::Gear)
-            .collect();
-    
-            log::debug!("    surroundings:");
-        for (p, t) in surrounding_symbols.clone() {
-            log::debug!("  {} {}", p, t);
-        }
-        assert_eq!("y:1 x:1 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[0].0, surrounding_symbols[0].1));
-        assert_eq!("y:1 x:3 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[1].0, surrounding_symbols[1].1));
-        assert_eq!("y:1 x:5 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[2].0, surrounding_symbols[2].1));
-        assert_eq!("y:3 x:1 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[3].0, surrounding_symbols[3].1));
-        assert_eq!("y:3 x:3 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[4].0, surrounding_symbols[4].1));
-        assert_eq!("y:3 x:5 Symbol: x".to_string(), format!("{} {}", surrounding_symbols[5].0, surrounding_symbols[5].1));
-        Ok(())
+        let schematic = parse_schematic(input).unwrap();
+
+        let id = schematic.tokens.id_at(Point::new(2, 2)).unwrap();
+        assert_eq!(vec![
+            "y:1 x:1 Symbol: x",
+            "y:1 x:3 Symbol: x",
+            "y:1 x:5 Symbol: x",
+            "y:3 x:1 Symbol: x",
+            "y:3 x:3 Symbol: x",
+            "y:3 x:5 Symbol: x",
+        ], describe(&schematic, schematic.tokens.touching(id, Connectivity::Eight)));
     }
 
     #[test]
-    fn test_gear_beside_number() -> Result<(), io::Error> {
+    fn test_gear_beside_number() {
         let input = "...........\n\
                      ....*359...\n\
                      .192.....*.";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-        let mut grid = Grid::new();
-        fill_map_from_text(lines, &mut grid)?;
-
-        let interesting_point = Point::new(1, 4);
-        let token = grid.tokens.get(&interesting_point).unwrap();
-        let surrounding_symbols: Vec<(Point, &Token)> = grid.find_surroundings(&interesting_point, token)
-            .into_iter()
-            .collect();
-    
-        println!("    surroundings:");
-        for (p, t) in surrounding_symbols.clone() {
-            println!("  {} {}", p, t);
-        }
-        assert_eq!("y:1 x:5 Numeric: 359".to_string(), format!("{} {}", surrounding_symbols[0].0, surrounding_symbols[0].1));
-        assert_eq!("y:2 x:1 Numeric: 192".to_string(), format!("{} {}", surrounding_symbols[1].0, surrounding_symbols[1].1));
-        Ok(())
+        let schematic = parse_schematic(input).unwrap();
+
+        let id = schematic.tokens.id_at(Point::new(1, 4)).unwrap();
+        assert_eq!(vec!["y:1 x:5 Numeric: 359", "y:2 x:1 Numeric: 192"],
+            describe(&schematic, schematic.tokens.touching(id, Connectivity::Eight)));
     }
-}
\ No newline at end of file
+}
@@ -1,21 +1,30 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::str::FromStr;
+
 use linked_hash_set::LinkedHashSet;
 
-fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "debug");
-    env_logger::init();
+use crate::error::{self, parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 
-    let file = File::open("res/y2023/day4_1.txt")?;
-    let reader = BufReader::new(file);
-    
-    let cards: Vec<Card> = parse_lines(Box::new(reader.lines()));
+pub struct Solver;
 
-    let point_sum = calculate_points_sum(cards);
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 4 }
+    fn part(&self) -> u8 { 1 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let cards: Vec<Card> = parse_lines(input)?;
+    measure::parsed();
 
-    println!("Summed points: {}", point_sum);
+    let point_sum = calculate_points_sum(cards);
 
-    Ok(())
+    Ok(Answer::from(point_sum))
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -27,8 +36,8 @@ struct Card {
 
 fn calculate_points_sum(cards: Vec<Card>) -> u32 {
     cards.into_iter()
-        .map(|card| calculate_winning_points(card))
-        .fold(0, |acc, num| acc + num)
+        .map(calculate_winning_points)
+        .sum()
 }
 
 fn calculate_winning_points(card: Card) -> u32 {
@@ -45,32 +54,33 @@ fn calculate_winning_points(card: Card) -> u32 {
     res
 }
 
-fn parse_lines(iterator: Box<dyn Iterator<Item=Result<String, std::io::Error>>>) -> Vec<Card> {
-    iterator
-        .map(|line| line.expect("Error reading line"))
-        .map(|line| parse_line(&line))
-        .collect()
+fn parse_lines(text: &str) -> Result<Vec<Card>, ParseError> {
+    error::parse_lines(text, Card::from_str)
 }
 
-fn parse_line(line: &str) -> Card {
-    let (card, nrs) = line.split_once(':').expect("Line should contain a ':'");
-    let card_id: u32 = card.strip_prefix("Card").expect("Line should start with 'Card '")
-        .trim()
-        .parse()
-        .expect(&format!("'{card}' should have been 'Card x'"));
-    let (winning_nrs, your_nrs) = nrs.split_once('|').expect("Right part of string should contain a '|'");
-    let winning_numbers: LinkedHashSet<u32> = winning_nrs.split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<u32>().expect(&format!("Could not parse {str}")))//&
-        .collect();
-    let your_numbers: LinkedHashSet<u32> = your_nrs.split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<u32>().expect(&format!("Could not parse {str}")))//&
-        .collect();
-    Card {
-        id: card_id, 
-        winnings: winning_numbers,
-        yours: your_numbers
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (card, nrs) = line.split_once(':')
+            .ok_or_else(|| ParseError::new("Line should contain a ':'"))?;
+        let card_id = card.strip_prefix("Card")
+            .ok_or_else(|| ParseError::at("Line should start with 'Card '", line, card))?
+            .trim();
+        let card_id: u32 = parse_number(line, card_id)?;
+        let (winning_nrs, your_nrs) = nrs.split_once('|')
+            .ok_or_else(|| ParseError::at("Right part of string should contain a '|'", line, nrs))?;
+        let winning_numbers: LinkedHashSet<u32> = winning_nrs.split_whitespace()
+            .map(|str| parse_number(line, str))
+            .collect::<Result<LinkedHashSet<u32>, ParseError>>()?;
+        let your_numbers: LinkedHashSet<u32> = your_nrs.split_whitespace()
+            .map(|str| parse_number(line, str))
+            .collect::<Result<LinkedHashSet<u32>, ParseError>>()?;
+        Ok(Card {
+            id: card_id, 
+            winnings: winning_numbers,
+            yours: your_numbers
+        })
     }
 }
 
@@ -82,16 +92,14 @@ mod tests {
     fn test_calculate_points_sum() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
                      Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-        let cards = parse_lines(lines);
+        let cards = parse_lines(input).unwrap();
         let sum = calculate_points_sum(cards);
         assert_eq!(10, sum);
     }
     #[test]
     fn test_calculate_winnings() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let card = parse_line(input);
+        let card = Card::from_str(input).unwrap();
 
         let points = calculate_winning_points(card);
         assert_eq!(8, points);
@@ -100,10 +108,7 @@ mod tests {
     fn test_parse_lines() {
         let input = "Card 1: 1 | 2\n\
                      Card 2: 3 | 4";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-         
-        let actual = parse_lines(lines);
+        let actual = parse_lines(input).unwrap();
         let expected = vec![
             Card {id: 1,
                 winnings: vec![1].into_iter().collect(),
@@ -118,7 +123,7 @@ mod tests {
     #[test]
     fn test_parse_line2() {
         let input = "Card    1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let actual = parse_line(input);
+        let actual = Card::from_str(input).unwrap();
         let expected = Card {
             id: 1,
             winnings: vec![41, 48, 83, 86, 17].into_iter().collect(),
@@ -130,7 +135,7 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let actual = parse_line(input);
+        let actual = Card::from_str(input).unwrap();
         let expected = Card {
             id: 1,
             winnings: vec![41, 48, 83, 86, 17].into_iter().collect(),
@@ -1,15 +1,26 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::str::FromStr;
+
 use linked_hash_set::LinkedHashSet;
 
-fn main() -> std::io::Result<()> {
-    std::env::set_var("RUST_LOG", "debug");
-    env_logger::init();
+use crate::error::{self, parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 4 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
 
-    let file = File::open("res/y2023/day4_1.txt")?;
-    let reader = BufReader::new(file);
-    
-    let cards: Vec<Card> = parse_lines(Box::new(reader.lines()));
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let cards: Vec<Card> = parse_lines(input)?;
+    measure::parsed();
 
     //matching numbers on scratch cards cause the following cards to be duplicated
     //card 1 has 4 matches, so cards 2-5 get another instance.
@@ -17,14 +28,13 @@ fn main() -> std::io::Result<()> {
     //card 3 has 2 matches and exists 4 times, so you get 4 and 5 four times.
 
     //each card exists once at the start!
-    let card_counts: Vec<usize> = calculate_cards_counts(cards);
+    let card_counts: Vec<usize> = calculate_cards_counts(cards)?;
     // for c in card_counts {
     //     println!("{}", c);
     // }
     let sum: usize = card_counts.iter().sum();
-    println!("Summed counts: {}", sum);
 
-    Ok(())
+    Ok(Answer::from(sum))
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -34,14 +44,17 @@ struct Card {
     yours: LinkedHashSet<usize>
 }
 
-fn calculate_cards_counts(mut cards: Vec<Card>) -> Vec<usize> {
+fn calculate_cards_counts(mut cards: Vec<Card>) -> Result<Vec<usize>, Error> {
     //each card exists once at the start
     let mut card_counts: Vec<usize> = vec![1; cards.len()];
     //process each cards number
     //card numbers are processed 0 based
     for card_idx in 0..cards.len() {
         let card = cards.remove(0);
-        assert_eq!((card_idx + 1) as usize, card.id);
+        if card.id != card_idx + 1 {
+            let message = format!("Expected Card {}, but found Card {}", card_idx + 1, card.id);
+            return Err(ParseError::new(message).in_line(card_idx + 1).into());
+        }
 
         let count_of_current_card = card_counts[card_idx];
         let matches = calculate_matches(card);
@@ -50,13 +63,14 @@ fn calculate_cards_counts(mut cards: Vec<Card>) -> Vec<usize> {
             let next_idx = card_idx + next + 1;
             // println!("card_idx: {}, next: {}, next_idx: {}", card_idx, next, next_idx);
             if next_idx >= card_counts.len() {
-                panic!("Incorrect number of cards, trying to access idx {}, but have only {} cards", next_idx, card_counts.len());
+                return Err(Error::NoSolution(format!("Card {} wins a copy of card {}, but there are only {} cards",
+                    card_idx + 1, next_idx + 1, card_counts.len())));
             }
             //increment the next cards count by the amount of current card
             card_counts[next_idx] += count_of_current_card;
         }
     }
-    card_counts
+    Ok(card_counts)
 }
 
 fn calculate_matches(card: Card) -> usize {
@@ -65,32 +79,33 @@ fn calculate_matches(card: Card) -> usize {
         .count()
 }
 
-fn parse_lines(iterator: Box<dyn Iterator<Item=Result<String, std::io::Error>>>) -> Vec<Card> {
-    iterator
-        .map(|line| line.expect("Error reading line"))
-        .map(|line| parse_line(&line))
-        .collect()
+fn parse_lines(text: &str) -> Result<Vec<Card>, ParseError> {
+    error::parse_lines(text, Card::from_str)
 }
 
-fn parse_line(line: &str) -> Card {
-    let (card, nrs) = line.split_once(':').expect("Line should contain a ':'");
-    let card_id: usize = card.strip_prefix("Card").expect("Line should start with 'Card '")
-        .trim()
-        .parse()
-        .expect(&format!("'{card}' should have been 'Card x'"));
-    let (winning_nrs, your_nrs) = nrs.split_once('|').expect("Right part of string should contain a '|'");
-    let winning_numbers: LinkedHashSet<usize> = winning_nrs.split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<usize>().expect(&format!("Could not parse {str}")))//&
-        .collect();
-    let your_numbers: LinkedHashSet<usize> = your_nrs.split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<usize>().expect(&format!("Could not parse {str}")))//&
-        .collect();
-    Card {
-        id: card_id, 
-        winnings: winning_numbers,
-        yours: your_numbers
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (card, nrs) = line.split_once(':')
+            .ok_or_else(|| ParseError::new("Line should contain a ':'"))?;
+        let card_id = card.strip_prefix("Card")
+            .ok_or_else(|| ParseError::at("Line should start with 'Card '", line, card))?
+            .trim();
+        let card_id: usize = parse_number(line, card_id)?;
+        let (winning_nrs, your_nrs) = nrs.split_once('|')
+            .ok_or_else(|| ParseError::at("Right part of string should contain a '|'", line, nrs))?;
+        let winning_numbers: LinkedHashSet<usize> = winning_nrs.split_whitespace()
+            .map(|str| parse_number(line, str))
+            .collect::<Result<LinkedHashSet<usize>, ParseError>>()?;
+        let your_numbers: LinkedHashSet<usize> = your_nrs.split_whitespace()
+            .map(|str| parse_number(line, str))
+            .collect::<Result<LinkedHashSet<usize>, ParseError>>()?;
+        Ok(Card {
+            id: card_id, 
+            winnings: winning_numbers,
+            yours: your_numbers
+        })
     }
 }
 
@@ -103,10 +118,8 @@ mod tests {
         let input = "Card 1: 1 2 | 1 2 3\n\
                      Card 2: 2 3 | 3 4 5\n\
                      Card 3: 3 4 | 10 11";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-        let cards = parse_lines(lines);
-        let counts = calculate_cards_counts(cards);
+        let cards = parse_lines(input).unwrap();
+        let counts = calculate_cards_counts(cards).unwrap();
         // for c in counts {
         //     println!("{}", c);
         // }
@@ -119,7 +132,7 @@ mod tests {
     #[test]
     fn test_calculate_matches() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let card = parse_line(input);
+        let card = Card::from_str(input).unwrap();
 
         let points = calculate_matches(card);
         assert_eq!(4, points);
@@ -128,10 +141,7 @@ mod tests {
     fn test_parse_lines() {
         let input = "Card 1: 1 | 2\n\
                      Card 2: 3 | 4";
-        let lines = Box::new(input.split("\n")
-            .map(|str| Ok(str.to_string())));
-         
-        let actual = parse_lines(lines);
+        let actual = parse_lines(input).unwrap();
         let expected = vec![
             Card {id: 1,
                 winnings: vec![1].into_iter().collect(),
@@ -146,7 +156,7 @@ mod tests {
     #[test]
     fn test_parse_line2() {
         let input = "Card    1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let actual = parse_line(input);
+        let actual = Card::from_str(input).unwrap();
         let expected = Card {
             id: 1,
             winnings: vec![41, 48, 83, 86, 17].into_iter().collect(),
@@ -158,7 +168,7 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let actual = parse_line(input);
+        let actual = Card::from_str(input).unwrap();
         let expected = Card {
             id: 1,
             winnings: vec![41, 48, 83, 86, 17].into_iter().collect(),
@@ -166,4 +176,15 @@ mod tests {
         };
         assert_eq!(expected, actual);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_invalid_cards() {
+        let error = parse_lines("Card 1: 1 | 2\n\
+                                 Card 2: 3 | x4").unwrap_err();
+        assert_eq!("line 2, column 13: 'x4' is not a valid number", error.to_string());
+        let cards = parse_lines("Card 1: 1 | 1\n\
+                                 Card 3: 3 | 4").unwrap();
+        assert_eq!(Err(Error::Parse(ParseError::new("Expected Card 2, but found Card 3").in_line(2))), calculate_cards_counts(cards));
+        let cards = parse_lines("Card 1: 1 | 1").unwrap();
+        assert!(matches!(calculate_cards_counts(cards), Err(Error::NoSolution(_))));
+    }
+}
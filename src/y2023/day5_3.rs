@@ -1,331 +1,186 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use std::collections::HashMap;
-use std::fmt;
-use peak_alloc::PeakAlloc;
+use std::iter::Enumerate;
+use std::str::Lines;
 
-#[global_allocator]
-static PEAK_ALLOC: PeakAlloc = PeakAlloc;
+use crate::error::{parse_number, Error, ParseError};
+use crate::graph::Graph;
+use crate::interval::{Interval, IntervalSet, RangeMap};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 
-const DEBUG_PRINTLN: bool = false;
+pub struct Solver;
 
-#[macro_export]
-macro_rules! debug {
-    ($($x:tt)*) => { if DEBUG_PRINTLN { println!($($x)*); } }
-}
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 5 }
+    fn part(&self) -> u8 { 2 }
 
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day5_1.txt")?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines()
-        .map(|line| match line {
-            Ok(line) => line,
-            Err(e) => panic!("Error reading line: {}", e)
-        });
-    let almanac: Almanac = parse_almanac(Box::new(lines));
-
-    let mut location_destinations: Vec<Vec<TruncatingRange>> = vec![];
-    for (from, len) in almanac.seeds.clone() {
-        let to = from + len - 1;
-        debug!("calc from-to {}-{}", from, to);
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "location"), TruncatingRange(from, to));
-        debug!("{:?}", dest);
-        location_destinations.push(dest);
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
     }
-
-    println!("location_destinations: {:?}", location_destinations);
-    
-    let min_location: usize = location_destinations.into_iter()
-        .flat_map(|vec| vec.into_iter())
-        .map(|range| range.0) //map to from
-        .min().expect("Should have found min.");
-    println!("min location: {:?}", min_location);
-
-
-	let current_mem = PEAK_ALLOC.current_usage_as_mb();
-	println!("This program currently uses {} MB of RAM.", current_mem);
-	println!("The max amount that was used:");
-	let peak_mem = PEAK_ALLOC.peak_usage_as_kb();
-	println!(" - {} KB", peak_mem);
-	let peak_mem = PEAK_ALLOC.peak_usage_as_mb();
-	println!(" - {} MB", peak_mem);
-
-    //just calculate all valid maps for each map,
-    //we could theoretically implement a backtracking algorithm, but won't need it
-
-    //first step is to view ranges differently:
-    //view each seed start range independently, and check for each if it reaches the next map layer, ommit if out of range
-    //valid ranges from - to
-    //initial seeds: 79 - 93
-    //accepted seeds: 98 - 99            => this means all seeds inside these ranges might be relevant
-    //              : 50 - 97
-    //mapped to soil: 50 - 51
-    //              : 52 - 99
-    //accepted soil : 15 - 51
-    //              : 52 - 53
-    //              :  0 - 14
-    //
-
-    Ok(())
-//refactoring to a Range approach.
-//  goal is calculating the possible ranges for each 'x-to-y map',
-//  and then backtracking from the smallest possible 'location' map value. "
-//
-//there also seems to be a memory leak, as I am at 6GB ram right now, increasing :D
 }
-//question:
-//get soil nr for my seed:
-//98 for 50, 99 for 51
-//
-// almanac, "seed", "fertilizer", 79);
-
-
-//only works single, e.g.
-fn find_destination_range(almanac: &Almanac, from_to: (&str, &str), start_range: TruncatingRange) -> Vec<TruncatingRange> {
-    let bridges: Vec<(&str, &str)> = find_bridging_maps(almanac, from_to.0, from_to.1);
-
-    let mut current_source_range: Vec<TruncatingRange> = vec![start_range];
-    //now iterate all bridges, and find the destination map for it,
-    //add all dest/to values
-    for (from, to) in bridges {
-        let map = match almanac.maps.get(from) {
-            Some(map) => map,
-            None => panic!("No Map defined for {}", from)
-        };
-        debug!("briding: {} - {}", from, to);
-        //ask the map for each range for all intersections
-        let mapped_ranges: Vec<TruncatingRange> = current_source_range.into_iter()
-            .map(|range| map.calculate_intersecting_ranges(range))
-            .flat_map(|vec| vec.into_iter())
-            .collect();
-        current_source_range = mapped_ranges;
-    }
 
-    current_source_range
-}
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let almanac: Almanac = parse_almanac(input)?;
+    measure::parsed();
 
-//truncating ranges can be truncated using AlmanecRanges, resulting in zero to multiple extra ranges
-#[derive(PartialEq, Eq)]
-struct TruncatingRange(usize, usize);
-impl fmt::Debug for TruncatingRange {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "tr({}, {})", self.0, self.1)
-    }
-}
+    //view each seed range as a whole instead of single seeds,
+    //the composed map splits and shifts the ranges, values out of its rules are mapped 1 to 1
+    let seeds: IntervalSet = almanac.seed_ranges()?.into_iter().collect();
+    let locations = find_destination_range(&almanac, ("seed", "location"), &seeds)?;
+    log::debug!("locations: {}", locations);
 
+    let min_location = locations.min()
+        .ok_or_else(|| Error::NoSolution("no seed range reaches a location".to_string()))?;
+    Ok(Answer::from(min_location))
+}
 
-struct Almanac {//almanac manager/handler
-    seeds: Vec<(usize, usize)>,
-    //maps source to Map providing source -> target ranges
-    maps: HashMap<String, AlmanacMap>
-    //not sure if Map<str, Vec<AlmanacMap>> would be required, or if these are 1-1 mappings
+fn find_destination_range(almanac: &Almanac, from_to: (&str, &str), start_range: &IntervalSet) -> Result<IntervalSet, Error> {
+    let mapping = almanac.mapping(from_to.0, from_to.1)?;
+    Ok(mapping.ranges.map(start_range))
 }
 
-#[derive(Debug)]
-struct AlmanacMap {
-    from: String,
-    to: String,
-    conversion_ranges: Vec<AlmanacRange>
+pub struct Almanac {//almanac manager/handler
+    //the numbers of the seeds line, part 1 reads them as single seeds
+    pub seeds: Vec<i64>,
+    //in the order of the text, at most one map per pair of categories
+    maps: Vec<AlmanacMap>,
+    //an edge for every map, a category may have maps to several others
+    categories: Graph<String>
 }
 
-impl AlmanacMap {
-    fn calculate_intersecting_ranges(&self, input_range: TruncatingRange) -> Vec<TruncatingRange> {
-        let mut result: Vec<TruncatingRange> = vec![];
-        //check each self.range
-        let mut current_unhandled_ranges: Vec<TruncatingRange> = vec![input_range];
-        for dest_range in &self.conversion_ranges {
-            //for intersection with input.range
-            if current_unhandled_ranges.is_empty() {
-                break;
-            }
-            // if let Some((handled_ranges, unhandled_ranges)) = dest_range.truncate_ranges(current_unhandled_ranges) {
-            let (mut handled_ranges, unhandled_ranges) = dest_range.truncate_ranges(current_unhandled_ranges);
-            //get the intersection split result and append it to the result.
-            //handled_ranges contains all resulting TruncatingRanges, mapped from Source to Destination
-            result.append(&mut handled_ranges);
-            //unhandled_ranges can be empty
-            current_unhandled_ranges = unhandled_ranges;
+impl Almanac {
+    /// The seeds as part 2 reads them, in pairs of start and length.
+    pub fn seed_ranges(&self) -> Result<Vec<Interval>, ParseError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(ParseError::new("Seeds should come in pairs of start and length").in_line(1));
         }
-        //at the end, append all unhandled ranges, as their mapped 1-1
-        result.append(&mut current_unhandled_ranges);
-        return result;
+        let ranges = self.seeds.chunks(2)
+            .map(|pair| Interval::with_length(pair[0], pair[1]))
+            .collect();
+        return Ok(ranges);
+    }
+
+    /// All maps on the way from `from` to `to` composed into one,
+    /// e.g. seed-to-soil, soil-to-fertilizer, ... become a single seed-to-location map.
+    /// No solution if no chain of maps leads there.
+    pub fn mapping(&self, from: &str, to: &str) -> Result<AlmanacMap, Error> {
+        let bridges: Vec<(&str, &str)> = find_bridging_maps(self, from, to)?;
+
+        let mut mapping = AlmanacMap {
+            from: from.to_string(),
+            to: from.to_string(),
+            ranges: RangeMap::new()
+        };
+        for (from, to) in bridges {
+            let map = self.map(from, to).expect("bridges follow the maps");
+            mapping = mapping.then(map);
+        }
+        Ok(mapping)
+    }
+
+    fn map(&self, from: &str, to: &str) -> Option<&AlmanacMap> {
+        self.maps.iter().find(|map| map.from == from && map.to == to)
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct AlmanacRange {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlmanacMap {
+    from: String,
+    to: String,
     // seed-to-soil map:
     // 50 98 2
-    // 50 dest / soil
-    //equals
-    // 98 source / seed
     // soil 50 = seed 98
-    // soil 51 = seed 99xr
-    destination_range_start: usize, 
-    source_range_start: usize,
-    range_length: usize
+    // soil 51 = seed 99
+    ranges: RangeMap
 }
 
-impl AlmanacRange {
-    fn new(destination_range_start: usize, source_range_start: usize, range_length: usize) -> Self {
-        Self {
-            destination_range_start: destination_range_start, 
-            source_range_start: source_range_start,
-            range_length: range_length
-        }
+impl AlmanacMap {
+    /// The destination of a single value, in O(log n) of the number of ranges.
+    pub fn get(&self, source: i64) -> i64 {
+        self.ranges.get(source)
     }
 
-    
-    //cases: i - input, s - this
-    //1. ...iiii... equal ->            3-6
-    //   ...ssss...                     3-6
-    //
-    //2. ..iiii.... left shift          2-5
-    //   ....ssss..                     4-7
-    //
-    //3. ....iiii.. right shift         4-7
-    //   ..ssss....                     2-5
-    //
-    //4. ...iiii... total included      3-6
-    //   ..ssssss..                     2-7
-    //
-    //5. ..iiiiii.. partly included     2-7
-    //   ...ssss...                     3-6
-    //
-    //6. .....iii.. not included
-    //   ..sss.....
-    //
-    //7. ..iii..... not included
-    //   .....sss..
-    //holy shit, it calculated the correct answer in 1h18m xD
-    fn truncate_ranges(&self, input_ranges: Vec<TruncatingRange>) -> (Vec<TruncatingRange>, Vec<TruncatingRange>) {
-        let mut handled_ranges: Vec<TruncatingRange> = vec![];
-        let mut unhandled_ranges: Vec<TruncatingRange> = vec![];
-
-        let diff: i64 = (self.destination_range_start as i64) - (self.source_range_start as i64);
-        debug!(" +> diff: {}", diff);
-        //subtract 1, as the length is the total length, cause start is inclusive
-        let source_range_end = self.source_range_start + self.range_length - 1; 
-        for unhandled_range in input_ranges {
-            debug!("  unhandled range: {:?}", unhandled_range);
-            //get intersection part
-            let TruncatingRange(in_from, in_to) = unhandled_range;
-            //if one of in_ numbers is inside the source range, 
-            if in_from >= self.source_range_start && in_from < source_range_end {
-                //case 1, 3, 4
-                if in_to >= self.source_range_start && in_to <= source_range_end {
-                    debug!(" case 1/4:");
-                    //case 1, 4 -> transform range numbers, no splitting
-                    let new_range = TruncatingRange(((in_from as i64) + diff) as usize, ((in_to as i64) + diff) as usize);
-                    debug!("   - handled:   {:?}", new_range);
-                    handled_ranges.push(new_range);
-                } else {
-                    debug!(" case 3:");
-                    //case 3 -> unhandled_range overlapps this.range to the right!
-                    //split into two ranges:
-                    //handled: in_from - source_range_end (inclusive)
-                    let new_range = TruncatingRange(((in_from as i64) + diff) as usize, ((source_range_end as i64) + diff) as usize);
-                    let new_unhandled = TruncatingRange(source_range_end + 1, in_to);
-                    debug!("   - handled:   {:?}", new_range);
-                    debug!("   - unhandled: {:?}", new_unhandled);
-                    handled_ranges.push(new_range);
-                    //unhandled: source_range_end (exclusive) - in_to
-                    //shift + 1 to the right, as the unhandled "from" is exclusive
-                    unhandled_ranges.push(new_unhandled);
-                }
-            } else if in_from <= self.source_range_start && in_to >= self.source_range_start {
-                //case 2, 5
-                if in_to <= source_range_end {
-                    debug!(" case 2:");
-                    //case 2 -> unhandled_range overlapps this.range to the left!
-                    //split into two ranges:
-                    //unhandled: in_from - source_range_start (exclusive)
-                    //shift - 1 to the left, as the unhandled "to" is exclusive
-                    let new_unhandled = TruncatingRange(in_from, self.source_range_start - 1);
-                    let new_range = TruncatingRange(((self.source_range_start as i64) + diff) as usize, ((in_to as i64) + diff) as usize);
-                    debug!("   - handled:   {:?}", new_range);
-                    debug!("   - unhandled: {:?}", new_unhandled);
-                    unhandled_ranges.push(new_unhandled);
-                    //handled: source_range_start (inclusive) - in_to
-                    handled_ranges.push(new_range);
-                } else {
-                    debug!(" case 5:");
-                    //case 5 -> split into two unhandled, and 1 handled
-                    //combination of case 2 and 3:
-                    //unhandled: in_from - source_range_start (exclusive)
-                    let new_unhandled = TruncatingRange(in_from, self.source_range_start - 1);
-                    let new_range = TruncatingRange(((self.source_range_start as i64) + diff) as usize, ((source_range_end as i64) + diff) as usize);
-                    let new_unhandled2 = TruncatingRange(source_range_end + 1, in_to);
-                    debug!("   - handled:   {:?}", new_range);
-                    debug!("   - unhandled: {:?}", new_unhandled);
-                    debug!("   - unhandled2:{:?}", new_unhandled2);
-                    unhandled_ranges.push(new_unhandled);
-                    //handled: source_range_start - source_range_end
-                    handled_ranges.push(new_range);
-                    //unhandled: source_range_end (exclusive) - in_t
-                    unhandled_ranges.push(new_unhandled2);
-                }
-            } else {
-                debug!(" case 6/7:");
-                //case 6, 7 -> unhandled, just add to unhandled ranges
-                debug!("   - unhandled: {:?}", unhandled_range);
-                unhandled_ranges.push(unhandled_range);
-            }
+    /// This map followed by `next`, whose categories have to line up: seed-to-soil then soil-to-fertilizer.
+    pub fn then(&self, next: &AlmanacMap) -> AlmanacMap {
+        debug_assert_eq!(self.to, next.from, "maps don't line up");
+        AlmanacMap {
+            from: self.from.clone(),
+            to: next.to.clone(),
+            ranges: self.ranges.then(&next.ranges)
         }
+    }
 
-        return (handled_ranges, unhandled_ranges);
+    /// The way back, e.g. location-to-seed answers which seed gives a location.
+    /// None if two sources share a destination.
+    pub fn inverse(&self) -> Option<AlmanacMap> {
+        Some(AlmanacMap {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            ranges: self.ranges.inverse()?
+        })
     }
 }
 
-fn find_bridging_maps<'a>(almanac: &'a Almanac, from: &'a str, to: &'a str) -> Vec<(&'a str, &'a str)> {
-    let mut result: Vec<(&str, &str)> = vec![];
-    
-    let mut from = from;
-    loop {
-        let map = match almanac.maps.get(from) {
-            Some(map) => map,
-            None => panic!("Missing Mapping for from: {}", from)
-        };
-        result.push((&map.from, &map.to));
-        if map.to == to {
-            break;
+/// The categories on the shortest chain of maps from `from` to `to`, as pairs of each map.
+fn find_bridging_maps<'a>(almanac: &'a Almanac, from: &str, to: &str) -> Result<Vec<(&'a str, &'a str)>, Error> {
+    for category in [from, to] {
+        if !almanac.categories.contains(&category.to_string()) {
+            return Err(Error::NoSolution(format!("no map from or to '{}'", category)));
         }
-        from = &map.to;
     }
+    let path: Vec<&String> = almanac.categories.shortest_path(&from.to_string(), &to.to_string())
+        .ok_or_else(|| Error::NoSolution(format!("no maps lead from '{}' to '{}'", from, to)))?;
 
-    return result;
+    let result = path.windows(2)
+        .map(|pair| (pair[0].as_str(), pair[1].as_str()))
+        .collect();
+    return Ok(result);
 }
 
-
 //almanac is the 'newspaper' containing the crop and weather information for farmers
-fn parse_almanac<'a>(mut lines: Box<dyn Iterator<Item=String>>) -> Almanac {
-    let seeds_line = lines.next().expect("'seeds' line required");
-    let seeds: Vec<(usize, usize)> = parse_seed_line(&seeds_line);
-    assert_eq!("", lines.next().expect("expected empty line spacing"), "expected empty line");
-    let maps: HashMap<String, AlmanacMap> = parse_almanac_maps(lines);
-    //first line should contain 
-
-    Almanac {
-        seeds: seeds,
-        maps: maps
+pub fn parse_almanac(text: &str) -> Result<Almanac, ParseError> {
+    let mut lines = text.lines().enumerate();
+    let (_, seeds_line) = lines.next().ok_or_else(|| ParseError::new("'seeds' line required").in_line(1))?;
+    let seeds: Vec<i64> = parse_seed_line(seeds_line).map_err(|e| e.in_line(1))?;
+    if lines.next().is_some_and(|(_, line)| !line.is_empty()) {
+        return Err(ParseError::new("expected empty line after the seeds").in_line(2));
+    }
+    let maps: Vec<AlmanacMap> = parse_almanac_maps(lines)?;
+
+    let mut categories: Graph<String> = Graph::new();
+    for map in &maps {
+        categories.add_edge(map.from.clone(), map.to.clone());
     }
+    //a chain of maps leading back to its start makes no sense for categories
+    if let Some(cycle) = categories.find_cycle() {
+        let cycle: Vec<&str> = cycle.into_iter().map(|category| category.as_str()).collect();
+        return Err(ParseError::new(format!("Maps form a cycle: {}", cycle.join(" -> "))));
+    }
+
+    Ok(Almanac {
+        seeds: seeds,
+        maps: maps,
+        categories: categories
+    })
 }
 
-fn parse_almanac_maps<'a>(lines: Box<dyn Iterator<Item=String>>) -> HashMap<String, AlmanacMap> {
-    let mut res: HashMap<String, AlmanacMap> = HashMap::new();
+fn parse_almanac_maps(lines: Enumerate<Lines>) -> Result<Vec<AlmanacMap>, ParseError> {
     //this shitty temp vec is required as the for loop takes ownership of the lines iter, 
     //and I can't just reuse the iter in the nested parse method parse_almanac_map (singular)
     // let mut last_key: Option<(&'a str, &'a str)> = None;
     
     let mut maps: Vec<AlmanacMap> = vec![];
 
-    for line in lines {
+    for (idx, line) in lines {
         if line.ends_with("map:") {
-            let (from, to) = parse_map_name(line);
+            let (from, to) = parse_map_name(line).map_err(|e| e.in_line(idx + 1))?;
+            if maps.iter().any(|map| map.from == from && map.to == to) {
+                return Err(ParseError::new(format!("Second map from '{}' to '{}'", from, to)).in_line(idx + 1));
+            }
             let new_map = AlmanacMap {
                 from: from,
                 to: to,
-                conversion_ranges: vec![]
+                ranges: RangeMap::new()
             };
             maps.push(new_map);
             // res.insert((&new_map.from, &new_map.to), new_map);
@@ -336,71 +191,51 @@ fn parse_almanac_maps<'a>(lines: Box<dyn Iterator<Item=String>>) -> HashMap<Stri
         } else if line.starts_with(|c: char| c.is_numeric()) {
             //it's a range
             if let Some(map) = maps.last_mut() {
-                let range = parse_range(line);
-                map.conversion_ranges.push(range);
+                let (destination, source, length) = parse_range(line).map_err(|e| e.in_line(idx + 1))?;
+                map.ranges.push(destination, source, length);
             } else {
-                panic!("There was no last map to add to, line: {}", line);
+                return Err(ParseError::new("Range before the first map name").in_line(idx + 1));
             }
         } else {
-            panic!("Line should end with 'map:' but was '{}'", line);
+            return Err(ParseError::new(format!("Line should end with 'map:' but was '{}'", line)).in_line(idx + 1));
         }
     }
 
-    for map in maps {
-        let from = map.from.clone();
-        let prev_val = res.insert(from.clone(), map);
-        if prev_val.is_some() {
-            panic!("Unhandled case, key '{}' existed already, and was mapped to '{:?}'", from, prev_val.unwrap());
-        }
-    }
-
-    return res;
+    return Ok(maps);
 }
 
-fn parse_range(line: String) -> AlmanacRange {
-    let split: Vec<usize> = line.split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<usize>().expect(&format!("Could not parse {str}")))//&
-        .collect();
-    return AlmanacRange::new(split[0], split[1], split[2]);
+/// `destination source length`
+fn parse_range(line: &str) -> Result<(i64, i64, i64), ParseError> {
+    let split: Vec<i64> = line.split_whitespace()
+        .map(|str| parse_number(line, str))
+        .collect::<Result<Vec<i64>, ParseError>>()?;
+    if split.len() != 3 {
+        return Err(ParseError::new(format!("Range should have 3 numbers, but had {}", split.len())));
+    }
+    return Ok((split[0], split[1], split[2]));
 }
 
-fn parse_map_name(line: String) -> (String, String) {
+fn parse_map_name(line: &str) -> Result<(String, String), ParseError> {
     let split = line.strip_suffix("map:")
-        .expect("line should be suffixed with 'map:'")
+        .ok_or_else(|| ParseError::new("line should be suffixed with 'map:'"))?
         .trim()
         .split_once("-to-")
-        .expect("from and to should be separated by '-to-'");
+        .ok_or_else(|| ParseError::new("from and to should be separated by '-to-'").with_column(1))?;
 
-    return (split.0.to_string(), split.1.to_string());
+    return Ok((split.0.to_string(), split.1.to_string()));
 }
 
-fn parse_seed_line(line: &str) -> Vec<(usize, usize)> {
-    let mut number_iterator = line.strip_prefix("seeds:").expect("Line should start with 'seeds:'")
+fn parse_seed_line(line: &str) -> Result<Vec<i64>, ParseError> {
+    line.strip_prefix("seeds:").ok_or_else(|| ParseError::new("Line should start with 'seeds:'"))?
         .split_whitespace()
-        .into_iter()
-        .map(|str| str.parse::<usize>().expect(&format!("Could not parse {str}")))
-        .collect::<Vec<usize>>()
-        .into_iter();
-
-    let mut ranges: Vec<(usize, usize)> = vec![];
-    loop {
-        let start = match number_iterator.next() {
-            Some(val) => val,
-            None => break //no more values
-        }; 
-        let count = match number_iterator.next() {
-            Some(val) => val,
-            None => panic!("Seed lines have to be dividable by two!")
-        }; 
-        ranges.push((start, count));
-    }
-    return ranges;
+        .map(|str| parse_number(line, str))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
 
     #[test]
     fn test_find_bridging_maps() {
@@ -414,21 +249,74 @@ mod tests {
                      0 15 37\n\
                      37 52 2\n\
                      39 0 15";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-
-        let almanac: Almanac = parse_almanac(lines);
+        let almanac: Almanac = parse_almanac(input).unwrap();
 
-        let bridges = find_bridging_maps(&almanac, "seed", "soil");
+        let bridges = find_bridging_maps(&almanac, "seed", "soil").unwrap();
         assert_eq!(1, bridges.len());
         assert_eq!(("seed", "soil"), bridges[0]);
         
-        let bridges = find_bridging_maps(&almanac, "seed", "fertilizer");
+        let bridges = find_bridging_maps(&almanac, "seed", "fertilizer").unwrap();
         assert_eq!(2, bridges.len());
         assert_eq!(("seed", "soil"), bridges[0]);
         assert_eq!(("soil", "fertilizer"), bridges[1]);
     }
 
+    #[test]
+    fn test_branching_categories() {
+        //soil can go to water directly or via fertilizer
+        let input = "seeds: 79 14\n\
+                     \n\
+                     seed-to-soil map:\n\
+                     50 98 2\n\
+                     \n\
+                     soil-to-fertilizer map:\n\
+                     0 15 37\n\
+                     \n\
+                     fertilizer-to-water map:\n\
+                     49 53 8\n\
+                     \n\
+                     soil-to-water map:\n\
+                     10 0 5\n\
+                     \n\
+                     soil-to-light map:\n\
+                     1 2 3";
+        let almanac: Almanac = parse_almanac(input).unwrap();
+
+        let bridges = find_bridging_maps(&almanac, "seed", "water").unwrap();
+        assert_eq!(vec![("seed", "soil"), ("soil", "water")], bridges);
+        let bridges = find_bridging_maps(&almanac, "seed", "light").unwrap();
+        assert_eq!(vec![("seed", "soil"), ("soil", "light")], bridges);
+        let bridges = find_bridging_maps(&almanac, "fertilizer", "water").unwrap();
+        assert_eq!(vec![("fertilizer", "water")], bridges);
+        assert_eq!(13, almanac.mapping("seed", "water").unwrap().get(3));
+
+        let error = find_bridging_maps(&almanac, "water", "seed").unwrap_err();
+        assert_eq!(Error::NoSolution("no maps lead from 'water' to 'seed'".to_string()), error);
+        let error = almanac.mapping("seed", "location").unwrap_err();
+        assert_eq!("no solution: no map from or to 'location'", error.to_string());
+    }
+
+    #[test]
+    fn test_invalid_category_graph() {
+        let cyclic = "seeds: 79 14\n\
+                      \n\
+                      seed-to-soil map:\n\
+                      \n\
+                      soil-to-water map:\n\
+                      \n\
+                      water-to-soil map:";
+        let error = parse_almanac(cyclic).err().unwrap();
+        assert_eq!("Maps form a cycle: soil -> water -> soil", error.to_string());
+
+        let twice = "seeds: 79 14\n\
+                     \n\
+                     seed-to-soil map:\n\
+                     \n\
+                     seed-to-soil map:";
+        let error = parse_almanac(twice).err().unwrap();
+        assert_eq!("line 5: Second map from 'seed' to 'soil'", error.to_string());
+    }
+
     #[test]
     fn test_find_destination_range_with_briding() {
         let input = "seeds: 79 14 55 13\n\
@@ -447,10 +335,7 @@ mod tests {
                      0 11 42\n\
                      42 0 7\n\
                      57 7 4";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-
-        let almanac: Almanac = parse_almanac(lines);
+        let almanac: Almanac = parse_almanac(input).unwrap();
 
         //difference to before:
         //don't look at each range, instead check for each range if they intersect, and create intersection ranges.
@@ -485,26 +370,61 @@ mod tests {
         //              : 52 - 53
         //              :  0 - 14
 
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "soil"), TruncatingRange(79, 92));
-        assert_eq!(1, dest.len());
-        assert_eq!(TruncatingRange(81, 94), dest[0]);
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "fertilizer"), TruncatingRange(79, 92));
-        assert_eq!(1, dest.len());
-        assert_eq!(TruncatingRange(81, 94), dest[0]);
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "water"), TruncatingRange(79, 92));
-        assert_eq!(1, dest.len());
-        assert_eq!(TruncatingRange(81, 94), dest[0]);
-        
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "soil"), TruncatingRange(55, 67));
-        assert_eq!(1, dest.len());
-        assert_eq!(TruncatingRange(57, 69), dest[0]);
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "fertilizer"), TruncatingRange(55, 67));
-        assert_eq!(1, dest.len());
-        assert_eq!(TruncatingRange(57, 69), dest[0]);
-        let dest: Vec<TruncatingRange> = find_destination_range(&almanac, ("seed", "water"), TruncatingRange(55, 67));
-        assert_eq!(2, dest.len());
-        assert_eq!(TruncatingRange(53, 56), dest[0]);
-        assert_eq!(TruncatingRange(61, 69), dest[1]);
+        let seeds = IntervalSet::from(Interval::inclusive(79, 92));
+        let expected = IntervalSet::from(Interval::inclusive(81, 94));
+        assert_eq!(expected, find_destination_range(&almanac, ("seed", "soil"), &seeds).unwrap());
+        assert_eq!(expected, find_destination_range(&almanac, ("seed", "fertilizer"), &seeds).unwrap());
+        assert_eq!(expected, find_destination_range(&almanac, ("seed", "water"), &seeds).unwrap());
+
+        let seeds = IntervalSet::from(Interval::inclusive(55, 67));
+        let expected = IntervalSet::from(Interval::inclusive(57, 69));
+        assert_eq!(expected, find_destination_range(&almanac, ("seed", "soil"), &seeds).unwrap());
+        assert_eq!(expected, find_destination_range(&almanac, ("seed", "fertilizer"), &seeds).unwrap());
+        let dest = find_destination_range(&almanac, ("seed", "water"), &seeds).unwrap();
+        assert_eq!(&[Interval::inclusive(53, 56), Interval::inclusive(61, 69)], dest.intervals());
+    }
+
+    #[test]
+    fn test_mapping() {
+        let input = examples::load(2023, 5, 2).unwrap();
+        let almanac: Almanac = parse_almanac(&input).unwrap();
+
+        // Seed number 79 corresponds to soil number 81, 14 to 14, 55 to 57 and 13 to 13.
+        let seed_to_soil = almanac.mapping("seed", "soil").unwrap();
+        assert_eq!(almanac.map("seed", "soil"), Some(&seed_to_soil));
+        assert_eq!(vec![50, 81, 14, 57, 13], [98, 79, 14, 55, 13].map(|seed| seed_to_soil.get(seed)));
+
+        let seed_to_fertilizer = almanac.mapping("seed", "fertilizer").unwrap();
+        assert_eq!(("seed", "fertilizer"), (seed_to_fertilizer.from.as_str(), seed_to_fertilizer.to.as_str()));
+        assert_eq!(81, seed_to_fertilizer.get(79));
+        assert_eq!(53, seed_to_fertilizer.get(14));
+        assert_eq!(57, seed_to_fertilizer.get(55));
+        assert_eq!(52, seed_to_fertilizer.get(13));
+
+        // Seed 79, soil 81, fertilizer 81, water 81, light 74, temperature 78, humidity 78, location 82.
+        let seed_to_location = almanac.mapping("seed", "location").unwrap();
+        assert_eq!(82, seed_to_location.get(79));
+        assert_eq!(43, seed_to_location.get(14));
+        assert_eq!(86, seed_to_location.get(55));
+        assert_eq!(35, seed_to_location.get(13));
+
+        //same as walking through every map
+        for seed in 0..120 {
+            let location = almanac.maps.iter().fold(seed, |value, map| map.get(value));
+            assert_eq!(location, seed_to_location.get(seed), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_inverse_mapping() {
+        let input = examples::load(2023, 5, 2).unwrap();
+        let almanac: Almanac = parse_almanac(&input).unwrap();
+
+        let location_to_seed = almanac.mapping("seed", "location").unwrap().inverse().unwrap();
+        assert_eq!(("location", "seed"), (location_to_seed.from.as_str(), location_to_seed.to.as_str()));
+        //the lowest location of part 2 comes from seed 82
+        assert_eq!(82, location_to_seed.get(46));
+        assert_eq!(13, location_to_seed.get(35));
     }
 
     #[test]
@@ -519,83 +439,28 @@ mod tests {
                     0 15 37\n\
                     37 52 2\n\
                     39 0 15";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-        
-        let almanac: Almanac = parse_almanac(lines);
-        assert_eq!(2, almanac.seeds.len());
-        
-        assert_eq!((79, 14), almanac.seeds[0]);
-        assert_eq!((55, 13), almanac.seeds[1]);
+        let almanac: Almanac = parse_almanac(input).unwrap();
+        assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
+        assert_eq!(vec![Interval::with_length(79, 14), Interval::with_length(55, 13)], almanac.seed_ranges().unwrap());
         
         assert_eq!(2, almanac.maps.len());
-        let map1: &AlmanacMap = almanac.maps.get(&"seed".to_string()).unwrap();
+        let map1: &AlmanacMap = almanac.map("seed", "soil").unwrap();
         assert_eq!("seed", map1.from);
         assert_eq!("soil", map1.to);
-        assert_eq!(2, map1.conversion_ranges.len());
-        assert_eq!(AlmanacRange::new(50, 98, 2), map1.conversion_ranges[0]);
-        assert_eq!(AlmanacRange::new(52, 50, 48), map1.conversion_ranges[1]);
+        assert_eq!(vec![(50, 98, 2), (52, 50, 48)].into_iter().collect::<RangeMap>(), map1.ranges);
+        assert_eq!(50, map1.ranges.get(98));
         
-        let map2: &AlmanacMap = almanac.maps.get(&"soil".to_string()).unwrap();
+        let map2: &AlmanacMap = almanac.map("soil", "fertilizer").unwrap();
         assert_eq!("soil", map2.from);
         assert_eq!("fertilizer", map2.to);
-        assert_eq!(3, map2.conversion_ranges.len());
-        assert_eq!(AlmanacRange::new(0, 15, 37), map2.conversion_ranges[0]);
-        assert_eq!(AlmanacRange::new(37, 52, 2), map2.conversion_ranges[1]);
-        assert_eq!(AlmanacRange::new(39, 0, 15), map2.conversion_ranges[2]);
-    }
-
-    #[test]
-    fn test_almanac_range_truncate_ranges() {
-        let r1 = AlmanacRange::new(10, 20, 30);
-        
-        //seed 98 => soil 50
-        let (handled_ranges, unhandled_ranges) = r1.truncate_ranges(vec![
-            TruncatingRange(10, 15), //not handled
-            TruncatingRange(15, 25), //only from 20
-            TruncatingRange(25, 45), //complete handled
-            TruncatingRange(45, 55), //onlt to 49, e.g. 50 values (20 + 30 value)
-            TruncatingRange(55, 65)  //onlt to 49, e.g. 50 values (20 + 30 value)
-        ]);
-        assert_eq!(4, unhandled_ranges.len());
-        assert_eq!(TruncatingRange(10, 15), unhandled_ranges[0]);
-        assert_eq!(TruncatingRange(15, 19), unhandled_ranges[1]);
-        assert_eq!(TruncatingRange(50, 55), unhandled_ranges[2]);
-        assert_eq!(TruncatingRange(55, 65), unhandled_ranges[3]);
-        
-        //handled ranges direclty translated! -10
-        assert_eq!(3, handled_ranges.len());
-        println!("{:?}", handled_ranges);
-        assert_eq!(TruncatingRange(10, 15), handled_ranges[0]);
-        assert_eq!(TruncatingRange(15, 35), handled_ranges[1]);
-        assert_eq!(TruncatingRange(35, 39), handled_ranges[2]);
-
-        let (handled_ranges, unhandled_ranges) = r1.truncate_ranges(vec![
-            TruncatingRange(10, 60), //2 unhandled, 1 handled
-            TruncatingRange(20, 50), //20-49 hanlded, 50 unhandled
-            TruncatingRange(20, 49), //complete handled
-            TruncatingRange(19, 50), //19 and 50 unhandled, 20-49 handled
-            TruncatingRange(30, 30), //only one handled
-        ]);
-        assert_eq!(5, unhandled_ranges.len());
-        assert_eq!(TruncatingRange(10, 19), unhandled_ranges[0]);
-        assert_eq!(TruncatingRange(50, 60), unhandled_ranges[1]);
-        assert_eq!(TruncatingRange(50, 50), unhandled_ranges[2]);
-        assert_eq!(TruncatingRange(19, 19), unhandled_ranges[3]);
-        assert_eq!(TruncatingRange(50, 50), unhandled_ranges[4]);
-        
-        assert_eq!(5, handled_ranges.len());
-        assert_eq!(TruncatingRange(10, 39), handled_ranges[0]);
-        assert_eq!(TruncatingRange(10, 39), handled_ranges[1]);
-        assert_eq!(TruncatingRange(10, 39), handled_ranges[2]);
-        assert_eq!(TruncatingRange(10, 39), handled_ranges[3]);
-        assert_eq!(TruncatingRange(20, 20), handled_ranges[4]);
+        assert_eq!(3, map2.ranges.rules().len());
+        assert_eq!(vec![(0, 15, 37), (37, 52, 2), (39, 0, 15)].into_iter().collect::<RangeMap>(), map2.ranges);
     }
 
     #[test]
     fn test_parse_map_name() {
-        let line = "seed-to-soil map:".to_string();
-        let (from, to) = parse_map_name(line);
+        let line = "seed-to-soil map:";
+        let (from, to) = parse_map_name(line).unwrap();
 
         assert_eq!("seed", from);
         assert_eq!("soil", to);
@@ -604,10 +469,14 @@ mod tests {
     #[test]
     fn test_parse_seed_line() {
         let line = "seeds: 79 14 55 13";
-        let seeds: Vec<(usize, usize)> = parse_seed_line(line);
-        assert_eq!(2, seeds.len());
-        
-        assert_eq!((79, 14), seeds[0]);
-        assert_eq!((55, 13), seeds[1]);
+        let seeds: Vec<i64> = parse_seed_line(line).unwrap();
+        assert_eq!(vec![79, 14, 55, 13], seeds);
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        let almanac: Almanac = parse_almanac("seeds: 79 14 55").unwrap();
+        assert_eq!(vec![79, 14, 55], almanac.seeds);
+        assert_eq!("line 1: Seeds should come in pairs of start and length", almanac.seed_ranges().unwrap_err().to_string());
     }
 }
\ No newline at end of file
@@ -1,60 +1,93 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-
-fn main() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day6_1.txt")?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines()
-        .map(|line| match line {
-            Ok(line) => line,
-            Err(e) => panic!("Error reading line: {}", e)
-        });
-    let race_sheet: RaceSheet = parse_input(Box::new(lines));
-    let races: Vec<(usize, usize)> = get_race_tuples(race_sheet);
+use crate::error::{parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 6 }
+    fn part(&self) -> u8 { 1 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let race_sheet: RaceSheet = parse_race_sheet(input, Kerning::Columns)?;
+    measure::parsed();
+    let races: Vec<(usize, usize)> = race_sheet.races();
 
     let mut result_sum = 1;
     for (time, distance) in races {
-        let range = calculate_winning_range((time, distance));
-        //plus one as start and end is included
-        println!("range: {:?}", range);
-        let count_winning_options = range.1 - range.0 + 1; 
-        println!(" - winning options: {}", count_winning_options);
+        let range = calculate_winning_range(time, distance);
+        log::debug!("range: {:?}", range);
+        let count_winning_options = count_winning_options(range);
+        log::debug!(" - winning options: {}", count_winning_options);
         result_sum *= count_winning_options; 
     }
 
-    println!("result: {}", result_sum);
+    Ok(Answer::from(result_sum))
+}
 
-    Ok(())
+/// How the numbers on the sheet are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kerning {
+    /// Every column is a race of its own, part 1.
+    Columns,
+    /// The spaces are just bad kerning, all digits of a line are a single race, part 2.
+    IgnoreSpaces,
 }
 
-struct RaceSheet {
+#[derive(Debug, PartialEq, Eq)]
+pub struct RaceSheet {
     times: Vec<usize>,
     distances: Vec<usize>
 }
 
-fn get_race_tuples(race_sheet: RaceSheet) -> Vec<(usize, usize)> {
-    if race_sheet.times.len() != race_sheet.distances.len() {
-        panic!("non matching time/distance lengths");
+impl RaceSheet {
+    /// The races as time and distance to beat.
+    pub fn races(&self) -> Vec<(usize, usize)> {
+        //parse_race_sheet checked that both have the same length
+        self.times.iter()
+            .copied()
+            .zip(self.distances.iter().copied())
+            .collect()
     }
-    race_sheet.times.into_iter()
-        .zip(race_sheet.distances.into_iter())
-        .collect()
 }
 
-fn calculate_winning_range(race: (usize, usize)) -> (usize, usize) {
+/// The first and last milliseconds to hold the button and beat `distance`, both included.
+/// None if no hold time wins.
+///
+/// Integers only, `f64` can't represent the squares of part 2 exactly.
+pub fn calculate_winning_range(time: usize, distance: usize) -> Option<(usize, usize)> {
     // (time - root(sqr(time) - 4 * min_distance)) / 2 //round up for start
     // (time + root(sqr(time) - 4 * min_distance)) / 2 //round down for end
-    let (time, distance) = race;
-    let time = time as f64;
+    let (time, distance) = (time as u128, distance as u128);
     // the distance to cover at least
-    let min_distance = (distance + 1) as f64;
+    let min_distance = distance + 1;
+    //the best is holding half the time, covering time^2 / 4
+    let discriminant = (time * time).checked_sub(4 * min_distance)?;
+
+    let wins = |hold: u128| hold * (time - hold) >= min_distance;
+    //the root is only exact for perfect squares, walk to the first winning hold time from there
+    let mut from = (time - discriminant.isqrt()) / 2;
+    while from > 0 && wins(from - 1) {
+        from -= 1;
+    }
+    while !wins(from) {
+        from += 1;
+    }
+    //symmetric, holding x or time - x covers the same distance
+    let to = time - from;
 
-    //round up for start
-    let from = (time - (time.powi(2) - (4 as f64) * min_distance).sqrt()) / (2 as f64);
-    //round down for end
-    let to = (time + (time.powi(2) - (4 as f64) * min_distance).sqrt()) / (2 as f64);
+    Some((from as usize, to as usize))
+}
 
-    (from.ceil() as usize, to.floor() as usize)
+/// Plus one as start and end is included.
+pub fn count_winning_options(range: Option<(usize, usize)>) -> usize {
+    range.map_or(0, |(from, to)| to - from + 1)
 }
 
 // again via ranges, 
@@ -107,65 +140,164 @@ fn calculate_winning_range(race: (usize, usize)) -> (usize, usize) {
     
     
 */
-fn parse_input(mut iterator: Box<dyn Iterator<Item=String>>) -> RaceSheet {
-    let times: Vec<usize> = iterator.next().expect("'Time' line missing")
-        .strip_prefix("Time:")
-        .expect("should start with 'Time:'")
-        .split_whitespace()
-        .into_iter()
-        .map(|nr| nr.parse::<usize>().expect("Could not parse nr."))
-        .collect();
-    let distances: Vec<usize> = iterator.next().expect("'Distance' line missing")
-        .strip_prefix("Distance:")
-        .expect("should start with 'Distance:'")
-        .split_whitespace()
-        .into_iter()
-        .map(|nr| nr.parse::<usize>().expect("Could not parse nr."))
-        .collect();
+/// Parses both parts' sheets, `kerning` tells how to read the numbers.
+///
+/// Either way every time needs a distance below it, a column without one is an error pointing at it.
+pub fn parse_race_sheet(text: &str, kerning: Kerning) -> Result<RaceSheet, ParseError> {
+    let mut lines = text.lines();
+    let (time_line, time_columns) = split_columns(lines.next(), "Time:")
+        .map_err(|e| e.in_line(1))?;
+    let times: Vec<usize> = parse_numbers(time_line, &time_columns, kerning)
+        .map_err(|e| e.in_line(1))?;
+    let (distance_line, distance_columns) = split_columns(lines.next(), "Distance:")
+        .map_err(|e| e.in_line(2))?;
+    let distances: Vec<usize> = parse_numbers(distance_line, &distance_columns, kerning)
+        .map_err(|e| e.in_line(2))?;
 
-    RaceSheet {
+    let message = format!("{} distances for {} times", distance_columns.len(), time_columns.len());
+    if let Some(time) = time_columns.get(distance_columns.len()) {
+        return Err(ParseError::at(format!("{}, no distance for '{}'", message, time), time_line, time).in_line(1));
+    }
+    if let Some(distance) = distance_columns.get(time_columns.len()) {
+        return Err(ParseError::at(format!("{}, no time for '{}'", message, distance), distance_line, distance).in_line(2));
+    }
+
+    Ok(RaceSheet {
         times: times,
         distances: distances
+    })
+}
+
+/// The line and the numbers after its `prefix`.
+fn split_columns<'a>(line: Option<&'a str>, prefix: &str) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+    let line = line.ok_or_else(|| ParseError::new(format!("'{}' line missing", prefix)))?;
+    let columns = line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(format!("should start with '{}'", prefix)).with_column(1))?
+        .split_whitespace()
+        .collect();
+    Ok((line, columns))
+}
+
+fn parse_numbers(line: &str, columns: &[&str], kerning: Kerning) -> Result<Vec<usize>, ParseError> {
+    match kerning {
+        Kerning::Columns => columns.iter()
+            .map(|nr| parse_number(line, nr))
+            .collect(),
+        Kerning::IgnoreSpaces => {
+            let digits = columns.concat();
+            //errors point at the first column, the digits aren't in the line as one
+            let start = columns.first().copied().unwrap_or(line);
+            digits.parse::<usize>()
+                .map(|nr| vec![nr])
+                .map_err(|_| ParseError::at(format!("'{}' is not a valid number", digits), line, start))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use crate::testutil::XorShift;
 
     #[test]
     fn test_calculate_winning_range() {
-        let range = calculate_winning_range((7, 9));
-        assert_eq!((2, 5), range);
-        let range = calculate_winning_range((15, 40));
-        assert_eq!((4, 11), range);
+        let range = calculate_winning_range(7, 9);
+        assert_eq!(Some((2, 5)), range);
+        let range = calculate_winning_range(15, 40);
+        assert_eq!(Some((4, 11)), range);
         //has to be bigger than the distance!, 
         //e.g. just add 1 to the distance to beat, to have a min-distance
-        let range = calculate_winning_range((30, 200)); 
-        assert_eq!((11, 19), range);
+        let range = calculate_winning_range(30, 200); 
+        assert_eq!(Some((11, 19)), range);
+
+        //holding 3 only ties
+        assert_eq!(None, calculate_winning_range(6, 9));
+        assert_eq!(Some((3, 3)), calculate_winning_range(6, 8));
+        assert_eq!(None, calculate_winning_range(0, 0));
+        assert_eq!(0, count_winning_options(None));
+    }
+
+    #[test]
+    fn test_calculate_winning_range_large() {
+        //f64 has 53 bits, time^2 needs 67 here
+        let time = 8_589_934_591_usize;
+        let distance = time / 2 * (time - time / 2) - 1;
+        assert_eq!(Some((time / 2, time - time / 2)), calculate_winning_range(time, distance));
+        assert_eq!(None, calculate_winning_range(time, distance + 1));
+
+        assert_eq!(Some((1, usize::MAX - 1)), calculate_winning_range(usize::MAX, 0));
+    }
+
+    /// The same races on every run.
+    fn random_races(count: usize, max_time: u64) -> Vec<(usize, usize)> {
+        let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+        (0..count)
+            .map(|_| {
+                let time = random.below(max_time + 1);
+                //up to a bit more than the best distance, to get races nobody wins
+                let distance = random.below(time * time / 4 + 3);
+                (time as usize, distance as usize)
+            })
+            .collect()
+    }
+
+    fn brute_force_winning_range(time: usize, distance: usize) -> Option<(usize, usize)> {
+        let wins = |hold: &usize| hold * (time - hold) > distance;
+        let from = (0..=time).find(wins)?;
+        let to = (0..=time).rev().find(wins)?;
+        Some((from, to))
+    }
+
+    #[test]
+    fn test_calculate_winning_range_matches_brute_force() {
+        for (time, distance) in random_races(2000, 300) {
+            assert_eq!(brute_force_winning_range(time, distance), calculate_winning_range(time, distance),
+                "time {} distance {}", time, distance);
+        }
+        //every distance of the small races, including all the ties
+        for time in 0..40 {
+            for distance in 0..=time * time / 4 + 1 {
+                assert_eq!(brute_force_winning_range(time, distance), calculate_winning_range(time, distance),
+                    "time {} distance {}", time, distance);
+            }
+        }
     }
 
     #[test]
     fn test_calculate_range() {
-        let input = "Time:      7  15   30\n\
-                     Distance:  9  40  200";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-        let sheet: RaceSheet = parse_input(lines);
+        let input = examples::load(2023, 6, 1).unwrap();
+        let sheet: RaceSheet = parse_race_sheet(&input, Kerning::Columns).unwrap();
 
-        let tuples: Vec<(usize, usize)> = get_race_tuples(sheet);
+        let tuples: Vec<(usize, usize)> = sheet.races();
         assert_eq!(vec![(7, 9), (15, 40), (30, 200)], tuples);
     }
 
     #[test]
     fn test_parse_input() {
-        let input = "Time:      7  15   30\n\
-                     Distance:  9  40  200";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-        let sheet: RaceSheet = parse_input(lines);
+        let input = examples::load(2023, 6, 1).unwrap();
+        let sheet: RaceSheet = parse_race_sheet(&input, Kerning::Columns).unwrap();
 
         assert_eq!(vec![7, 15, 30], sheet.times);
         assert_eq!(vec![9, 40, 200], sheet.distances);
+
+        let sheet: RaceSheet = parse_race_sheet(&input, Kerning::IgnoreSpaces).unwrap();
+        assert_eq!(vec![(71530, 940200)], sheet.races());
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_input_errors() {
+        let error = parse_race_sheet("Time:      7  15   30\n\
+                                      Distance:  9  40", Kerning::Columns).err().unwrap();
+        assert_eq!("line 1, column 20: 2 distances for 3 times, no distance for '30'", error.to_string());
+        let error = parse_race_sheet("Time:      7  15\n\
+                                      Distance:  9  40  200", Kerning::IgnoreSpaces).err().unwrap();
+        assert_eq!("line 2, column 19: 3 distances for 2 times, no time for '200'", error.to_string());
+        let error = parse_race_sheet("Time:      7  15   30", Kerning::Columns).err().unwrap();
+        assert_eq!("line 2: 'Distance:' line missing", error.to_string());
+        let error = parse_race_sheet("Time:      7  1.5   30", Kerning::Columns).err().unwrap();
+        assert_eq!("line 1, column 15: '1.5' is not a valid number", error.to_string());
+        let error = parse_race_sheet("Time:      7  1.5   30\n\
+                                      Distance:  9  40  200", Kerning::IgnoreSpaces).err().unwrap();
+        assert_eq!("line 1, column 12: '71.530' is not a valid number", error.to_string());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::interval::{Interval, IntervalSet};
+
+/// The boat race of day 6 with knobs, e.g. for showing how the winning hold times change.
+///
+/// Holding the button charges the boat by `charge_rate` per millisecond, up to `max_speed`.
+/// Once released it moves its speed each millisecond, after which `friction` slows it down,
+/// until it stands still or the race is over. The puzzle's boat is `RaceModel::new()`:
+/// charging by 1, no top speed and no friction, so it covers `hold * (time - hold)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceModel {
+    charge_rate: u32,
+    max_speed: Option<u32>,
+    friction: u32,
+}
+
+impl Default for RaceModel {
+    fn default() -> Self {
+        RaceModel { charge_rate: 1, max_speed: None, friction: 0 }
+    }
+}
+
+impl RaceModel {
+    /// The puzzle's boat.
+    pub fn new() -> RaceModel {
+        RaceModel::default()
+    }
+
+    pub fn with_charge_rate(mut self, charge_rate: u32) -> RaceModel {
+        self.charge_rate = charge_rate;
+        self
+    }
+
+    pub fn with_max_speed(mut self, max_speed: u32) -> RaceModel {
+        self.max_speed = Some(max_speed);
+        self
+    }
+
+    pub fn with_friction(mut self, friction: u32) -> RaceModel {
+        self.friction = friction;
+        self
+    }
+
+    /// The speed when releasing the button after `hold` milliseconds.
+    pub fn speed(&self, hold: i64) -> u128 {
+        let speed = self.charge_rate as u128 * hold.max(0) as u128;
+        self.max_speed.map_or(speed, |max_speed| speed.min(max_speed as u128))
+    }
+
+    /// The distance covered in a race of `time` milliseconds, 0 if `hold` isn't inside the race.
+    pub fn distance(&self, hold: i64, time: i64) -> u128 {
+        if hold < 0 || hold > time {
+            return 0;
+        }
+        let speed = self.speed(hold);
+        let moving = match self.friction {
+            0 => (time - hold) as u128,
+            friction => ((time - hold) as u128).min(speed.div_ceil(friction as u128)),
+        };
+        if moving == 0 {
+            return 0;
+        }
+        //the arithmetic series speed, speed - friction, ... over the milliseconds moving
+        let first_plus_last = 2 * speed - self.friction as u128 * (moving - 1);
+        return moving.saturating_mul(first_plus_last) / 2;
+    }
+
+    /// Whether friction stops the boat before the race is over.
+    fn stops_early(&self, hold: i64, time: i64) -> bool {
+        self.friction > 0 && self.speed(hold).div_ceil(self.friction as u128) < (time - hold) as u128
+    }
+
+    /// All hold times covering more than `distance`.
+    ///
+    /// The hold times are cut where the shape of the distance changes:
+    /// - stopped by friction, holding longer only makes the boat faster, it never covers less
+    /// - moving the whole race below top speed, the distance is a parabola
+    /// - at top speed, holding longer only takes time from moving
+    ///
+    /// and each piece is binary searched on its own, so `O(log time)` instead of every hold time.
+    pub fn winning_holds(&self, time: i64, distance: i64) -> IntervalSet {
+        let wins = |hold: i64| distance < 0 || self.distance(hold, time) > distance as u128;
+        let end = time.saturating_add(1).max(0);
+        let top_speed = first(0, end, |hold| self.max_speed.is_some_and(|max_speed| self.speed(hold) >= max_speed as u128));
+        let moving_whole_race = first(0, top_speed, |hold| !self.stops_early(hold, time));
+
+        let mut winning = vec![Interval::new(first(0, moving_whole_race, wins), moving_whole_race)];
+        if moving_whole_race < top_speed {
+            let peak = first(moving_whole_race, top_speed - 1, |hold| self.distance(hold + 1, time) <= self.distance(hold, time));
+            if wins(peak) {
+                let from = first(moving_whole_race, peak, wins);
+                let to = first(peak, top_speed, |hold| !wins(hold));
+                winning.push(Interval::new(from, to));
+            }
+        }
+        winning.push(Interval::new(top_speed, first(top_speed, end, |hold| !wins(hold))));
+        return winning.into_iter().collect();
+    }
+
+    /// `winning_holds` by trying every hold time, to check it on small races.
+    pub fn brute_force_winning_holds(&self, time: i64, distance: i64) -> IntervalSet {
+        (0..=time)
+            .filter(|hold| distance < 0 || self.distance(*hold, time) > distance as u128)
+            .map(|hold| Interval::new(hold, hold + 1))
+            .collect()
+    }
+}
+
+/// The first of `from..to` meeting `condition`, `to` if none does.
+/// Once met, `condition` has to stay met up to `to`.
+fn first(from: i64, to: i64, condition: impl Fn(i64) -> bool) -> i64 {
+    let (mut low, mut high) = (from, to.max(from));
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if condition(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    return low;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::XorShift;
+    use crate::y2023::day6_1::calculate_winning_range;
+
+    #[test]
+    fn test_distance() {
+        let model = RaceModel::new();
+        assert_eq!(vec![0, 6, 10, 12, 12, 10, 6, 0], (0..=7).map(|hold| model.distance(hold, 7)).collect::<Vec<_>>());
+        assert_eq!(0, model.distance(8, 7));
+
+        //holding 3 charges to 6, capped to 5, then 5 + 4 + 3
+        let model = RaceModel::new().with_charge_rate(2).with_max_speed(5).with_friction(1);
+        assert_eq!(vec![0, 3, 10, 12, 9, 5, 0], (0..=6).map(|hold| model.distance(hold, 6)).collect::<Vec<_>>());
+        assert_eq!(5, model.speed(1_000));
+    }
+
+    #[test]
+    fn test_winning_holds() {
+        let model = RaceModel::new().with_charge_rate(2).with_max_speed(5).with_friction(1);
+        assert_eq!("{2..4}", model.winning_holds(6, 9).to_string());
+        assert_eq!("{2..6}", model.winning_holds(6, 4).to_string());
+        assert_eq!("{}", model.winning_holds(6, 12).to_string());
+        assert_eq!("{0..7}", model.winning_holds(6, -1).to_string());
+        assert_eq!("{}", model.winning_holds(-1, -1).to_string());
+
+        //never charging, never moving
+        assert_eq!("{}", RaceModel::new().with_charge_rate(0).winning_holds(100, 0).to_string());
+    }
+
+    #[test]
+    fn test_puzzle_boat() {
+        let model = RaceModel::new();
+        for (time, distance) in [(7, 9), (15, 40), (30, 200), (71530, 940200), (6, 9)] {
+            let expected: IntervalSet = calculate_winning_range(time, distance)
+                .map(|(from, to)| Interval::inclusive(from as i64, to as i64))
+                .into_iter()
+                .collect();
+            assert_eq!(expected, model.winning_holds(time as i64, distance as i64));
+        }
+    }
+
+    #[test]
+    fn test_winning_holds_matches_brute_force() {
+        let mut random = XorShift::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let mut model = RaceModel::new().with_charge_rate(random.below(5) as u32).with_friction(random.below(4) as u32);
+            if random.below(2) == 0 {
+                model = model.with_max_speed(random.below(60) as u32);
+            }
+            let time = random.below(80) as i64;
+            let best = (0..=time).map(|hold| model.distance(hold, time)).max().unwrap() as u64;
+            for distance in [random.below(best + 2) as i64, best as i64, best as i64 - 1, 0] {
+                assert_eq!(model.brute_force_winning_holds(time, distance), model.winning_holds(time, distance),
+                    "{:?} time {} distance {}", model, time, distance);
+            }
+        }
+    }
+}
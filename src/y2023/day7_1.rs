@@ -1,31 +1,33 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use crate::error::{Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
+use crate::y2023::day7_rules::RuleSet;
 
-pub fn run() -> std::io::Result<()> {
-    let file = File::open("res/y2023/day7_1.txt")?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines()
-        .map(|line| match line {
-            Ok(line) => line,
-            Err(e) => panic!("Error reading line: {}", e)
-        });
+pub struct Solver;
 
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 7 }
+    fn part(&self) -> u8 { 1 }
 
-    let mut hands: Vec<(Hand, usize)> = parse_lines(Box::new(lines));
-    sort_hands_asc(&mut hands);
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let hands: Vec<(Hand, usize)> = parse_lines(input)?;
+    measure::parsed();
     //max rank = number of hands
     //define weakness of hand
     //weakest gets rank 1
     //rank * bid = winnings
+    let sum: usize = RuleSet::camel_cards().total_winnings(hands);
 
-    let sum: usize = hands.into_iter().enumerate()
-        .map(|(idx, (_, bid))| (idx + 1) * bid)
-        .sum();
-
-    println!("{:?}", sum);
-    Ok(())
+    Ok(Answer::from(sum))
 }
 
+/// Ordered by type, then by the first card that differs, see `RuleSet` for other orders.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Hand {
     pub typ: Typ, 
@@ -45,7 +47,7 @@ pub enum Typ {
     FiveOfAKind,
 }
 
-pub fn sort_hands_asc(hands: &mut Vec<(Hand, usize)>) {
+pub fn sort_hands_asc(hands: &mut [(Hand, usize)]) {
     //ignore the bids
     //order asc (a to b), desc is b to a
     hands.sort_by(|a, b| a.0.cmp(&b.0));
@@ -98,7 +100,7 @@ pub const fn initialize_typ_matrix() -> [[[[[Typ; 5]; 5]; 5]; 5]; 5] {
 /// 
 /// variants is the count of possible different cards passed
 pub const fn identify_hand_type(cards: [usize; 5]) -> Typ {
-    let mut occurrences = [0 as usize; 5];
+    let mut occurrences = [0_usize; 5];
     //card to occurrence count mapping
     let mut i = 0;
     while i < 5 {
@@ -145,64 +147,15 @@ pub const fn identify_hand_type(cards: [usize; 5]) -> Typ {
     return Typ::HighCard;
 }
 
-fn to_card(char: char) -> usize {
-    match char {
-        'A' => 12,
-        'K' => 11,
-        'Q' => 10,
-        'J' => 9,
-        'T' => 8,
-        '9' => 7,
-        '8' => 6,
-        '7' => 5,
-        '6' => 4,
-        '5' => 3,
-        '4' => 2,
-        '3' => 1,
-        '2' => 0,
-        _ => panic!("Unhandled Card char: {}", char)
-    }
-}
-
-fn parse_lines(lines: Box<dyn Iterator<Item=String>>) -> Vec<(Hand, usize)> {
-     lines.map(|line| parse_line(line))
-        .collect()
-}
-
-// 32T3K 765
-fn parse_line(line: String) -> (Hand, usize) {
-    let (hand, bid) = match line.split_once(' ') {
-        Some(tuple) => tuple,
-        None => panic!("line should contain exactly one blank space")
-    };
-
-    let cards: [usize; 5] = hand.chars()
-        .map(|c| to_card(c))
-        .collect::<Vec<usize>>()
-        .try_into()
-        .unwrap();
-
-    let cards_reduced_range = reduce_variant_range(cards);
-
-    let typ = TYP_MATRIX[cards_reduced_range[0]]
-                        [cards_reduced_range[1]]
-                        [cards_reduced_range[2]]
-                        [cards_reduced_range[3]]
-                        [cards_reduced_range[4]];
-    let hand = Hand {
-        typ: typ,
-        cards: cards
-    };
-    let bid = bid.parse::<usize>().expect("Should be a positive integer");
-
-    (hand, bid)
+fn parse_lines(text: &str) -> Result<Vec<(Hand, usize)>, ParseError> {
+    RuleSet::camel_cards().parse_lines(text)
 }
 
 /// Bench Results:
 pub fn reduce_variant_range(cards: [usize; 5]) -> [usize; 5] {
     //init with 8, which is > the max real value of 4
-    let mut variant_map = [8 as usize; 13];
-    let mut result = [0 as usize; 5];
+    let mut variant_map = [8_usize; 13];
+    let mut result = [0_usize; 5];
     //first is always 0, result[0] therefor also 0
     variant_map[cards[0]] = 0;
 
@@ -228,10 +181,7 @@ pub fn reduce_variant_range(cards: [usize; 5]) -> [usize; 5] {
         _ => variant_map[cards[3]]
     };
     result[4] = match variant_map[cards[4]] {
-        8 => {
-            variant_map[cards[4]] = 4;
-            4
-        },
+        8 => 4, //last card, no need to remember the mapping
         _ => variant_map[cards[4]]
     };
     return result;
@@ -240,6 +190,7 @@ pub fn reduce_variant_range(cards: [usize; 5]) -> [usize; 5] {
 #[cfg(test)]
 mod tests {
     use crate::y2023::day7_1::{*};
+    use crate::examples;
     use std::cmp::Ordering;
 
     #[test]
@@ -282,14 +233,7 @@ mod tests {
 
     #[test]
     fn test_sort_hands_asc() {
-        let input = "32T3K 765\n\
-                     T55J5 684\n\
-                     KK677 28\n\
-                     KTJJT 220\n\
-                     QQQJA 483";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-
+        let input = examples::load(2023, 7, 1).unwrap();
         /*
         So, the first step is to put the hands in order of strength:
 
@@ -301,7 +245,7 @@ mod tests {
             T55J5 and QQQJA are both three of a kind. QQQJA has 
                 a stronger first card, so it gets rank 5 and T55J5 gets rank 4.
          */
-        let mut hands = parse_lines(lines);
+        let mut hands = parse_lines(&input).unwrap();
         sort_hands_asc(&mut hands);
         //compare bids
         assert_eq!(765, hands[0].1);
@@ -359,10 +303,18 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let input = "32T3K 765";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = RuleSet::camel_cards().parse_line(input).unwrap();
 
         assert_eq!(Typ::OnePair, hand.typ);
         assert_eq!([1, 0, 8, 1, 11], hand.cards);
         assert_eq!(765, bid);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_line_errors() {
+        let rules = RuleSet::camel_cards();
+        assert_eq!("column 4: Unhandled Card char: X", rules.parse_line("32TXK 765").unwrap_err().to_string());
+        assert_eq!("column 1: Hand should have 5 cards, but had 4", rules.parse_line("32TK 765").unwrap_err().to_string());
+        assert_eq!("column 7: '-765' is not a valid number", rules.parse_line("32T3K -765").unwrap_err().to_string());
+        assert_eq!("line 2: line should contain exactly one blank space", parse_lines("32T3K 765\nT55J5").unwrap_err().to_string());
+    }
+}
@@ -55,7 +55,7 @@ pub const fn initialize_typ_matrix_bit_shift() -> [Typ; TYP_MATRIX_BIT_SHIFT_LEN
 
 pub const fn identify_hand_type(cards: usize) -> Typ {
     //card to occurrence count mapping
-    let mut occurrences = [0 as usize; 5];
+    let mut occurrences = [0_usize; 5];
     //card1 occurences
     occurrences[0] = 1; // 1. card (is always 0 and therefor alwats there)
     let card = cards & 0b000_00_00_1; //0 - 1
@@ -146,7 +146,7 @@ pub const fn identify_hand_type(cards: usize) -> Typ {
 /// return 0b01223_3444
 pub fn reduce_variant_range_bit_shift(cards: [usize; 5]) -> usize {
     //init with 8, which is > the max real value of 4
-    let mut variant_map = [8 as usize; 13];
+    let mut variant_map = [8_usize; 13];
     let mut result: usize = 0;
     //first is always 0, result[0] therefor also 0, and therefor can be omitted
     variant_map[cards[0]] = 0;
@@ -159,32 +159,30 @@ pub fn reduce_variant_range_bit_shift(cards: [usize; 5]) -> usize {
 
 
     //card 1 can be 0 or 1
-    result = result | match variant_map[cards[1]] {
+    result |= match variant_map[cards[1]] {
         8 => {
             variant_map[cards[1]] = 1;
             0b000_00_00_1
         },
         _ => 0 //only 0 is the other option //variant_map[cards[1]]
     };
-    result = result | match variant_map[cards[2]] {
+    result |= match variant_map[cards[2]] {
         8 => {
             variant_map[cards[2]] = 2;
             0b000_00_10_0
         },
         _ => variant_map[cards[2]] << 1 // 0 or 1, shift to the left 1, to skip the card[1] bytes
     };
-    result = result | match variant_map[cards[3]] {
+    result |= match variant_map[cards[3]] {
         8 => {
             variant_map[cards[3]] = 3;
             0b000_11_00_0
         },
         _ => variant_map[cards[3]] << 3 // skip the card[2] position, shift to pos of the 0b11 in 0b000_11_00_0
     };
-    result = result | match variant_map[cards[4]] {
-        8 => {
-            variant_map[cards[4]] = 4;
-            0b100_00_00_0 //TODO: will do with 8 bit for now, but I think it could be reduced to at least 7 bit
-        },
+    result |= match variant_map[cards[4]] {
+        //last card, no need to remember the mapping
+        8 => 0b100_00_00_0, //TODO: will do with 8 bit for now, but I think it could be reduced to at least 7 bit
         _ => variant_map[cards[4]] << 5
     };
 
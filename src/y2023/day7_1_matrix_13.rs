@@ -10,8 +10,8 @@ use crate::y2023::day7_1_slow_methods;
 //         371.293 matrix points * 3bit = 1.113.879 = 135kb 
 // (i incorrectly calculated the 5^5 matrix in day7_1.rs)
 pub const MATRIX_LEN: usize = 13;
-#[warn(long_running_const_eval)]
-pub const TYP_MATRIX: [[[[[Typ; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN] = initialize_typ_matrix_13();
+#[allow(long_running_const_eval)]
+pub static TYP_MATRIX: [[[[[Typ; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN] = initialize_typ_matrix_13();
 //lets go memory brute force
 //no init time, due to const fn!! calculate at compile time
 pub const fn initialize_typ_matrix_13() -> [[[[[Typ; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN]; MATRIX_LEN] {
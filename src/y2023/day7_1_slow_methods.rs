@@ -13,8 +13,8 @@ use crate::y2023::day7_1::*;
 //this has basically the same performance as the full static version
 pub fn reduce_variant_range_half_static(cards: [usize; 5]) -> [usize; 5] {
     //init with 8, which is > the max real value of 4
-    let mut variant_map = [8 as usize; 13];
-    let mut result = [0 as usize; 5];
+    let mut variant_map = [8_usize; 13];
+    let mut result = [0_usize; 5];
     //first is always 0, result[0] therefor also 0
     variant_map[cards[0]] = 0;
 
@@ -37,7 +37,7 @@ pub fn reduce_variant_range_half_static(cards: [usize; 5]) -> [usize; 5] {
 /// Not sure why this takes 5 times longer
 pub fn reduce_variant_range_slow(cards: [usize; 5]) -> [usize; 5] {
     //init with 8, which is > the max real value of 4
-    let mut variant_map = [8 as usize; 13];
+    let mut variant_map = [8_usize; 13];
     let mut counter = 0;
     for source_id in cards {
         let target_id = variant_map[source_id];
@@ -57,7 +57,7 @@ pub fn reduce_variant_range_slow(cards: [usize; 5]) -> [usize; 5] {
 }
 
 pub const fn identify_hand_type13(cards: [usize; 5]) -> Typ {
-    let mut occurrences = [0 as usize; 13];
+    let mut occurrences = [0_usize; 13];
     //card to occurrence count mapping
     let mut i = 0;
     while i < 5 {
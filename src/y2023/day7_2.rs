@@ -1,8 +1,22 @@
-use crate::y2023::day7_1::{Hand, Typ, sort_hands_asc};
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+use crate::error::{Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
+use crate::y2023::day7_1::{Hand, Typ};
+use crate::y2023::day7_rules::RuleSet;
 
-pub fn run() -> std::io::Result<()> {
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2023 }
+    fn day(&self) -> u8 { 7 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
     /*
     1. reorder, J < 2 < 3...
     2. change identify_hand_type, parse J as joker
@@ -10,129 +24,93 @@ pub fn run() -> std::io::Result<()> {
        and check from best to worst in teh identify hand function,
        counting all J's for each comparison
     */
-    let file = File::open("res/y2023/day7_1.txt")?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines()
-        .map(|line| match line {
-            Ok(line) => line,
-            Err(e) => panic!("Error reading line: {}", e)
-        });
 
-    
     //max rank = number of hands
     //define weakness of hand
     //weakest gets rank 1
     //rank * bid = winnings
-    
-    // print the parsed values and the original line
-    // let mut hands = parse_lines_with_line(Box::new(lines));
-    // sort_hands_asc_with_line(&mut hands);
-    // hands.into_iter()
-    //     .for_each(|((hand, bid), line)| 
-    //         println!("{:?} {} - {}", hand, bid, line));
-        
-    let mut hands = parse_lines(Box::new(lines));
-    sort_hands_asc(&mut hands);
-    let sum: usize = hands.into_iter().enumerate()
-        .map(|(idx, (_, bid))| (idx + 1) * bid)
-        .sum();
-    println!("{:?}", sum);
-    
-    Ok(())
-}
+    let hands = parse_lines(input)?;
+    measure::parsed();
+    let sum: usize = RuleSet::jokers().total_winnings(hands);
 
-pub fn sort_hands_asc_with_line(hands: &mut Vec<((Hand, usize), String)>) {
-    //ignore the bids
-    //order asc (a to b), desc is b to a
-    hands.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+    Ok(Answer::from(sum))
 }
 
-fn to_card(char: char) -> usize {
-    match char {
-        'A' => 12,
-        'K' => 11,
-        'Q' => 10,
-        'T' => 9,
-        '9' => 8,
-        '8' => 7,
-        '7' => 6,
-        '6' => 5,
-        '5' => 4,
-        '4' => 3,
-        '3' => 2,
-        '2' => 1,
-        'J' => 0,
-        _ => panic!("Unhandled Card char: {}", char)
-    }
-}
-
-fn parse_lines(lines: Box<dyn Iterator<Item=String>>) -> Vec<(Hand, usize)> {
-    lines.map(|line| parse_line(line))
-       .collect()
+fn parse_lines(text: &str) -> Result<Vec<(Hand, usize)>, ParseError> {
+    RuleSet::jokers().parse_lines(text)
 }
 
-fn parse_lines_with_line(lines: Box<dyn Iterator<Item=String>>) -> Vec<((Hand, usize), String)> {
-    lines.map(|line| (parse_line(line.clone()), line))
-       .collect()
-}
+/// Reduced cards plus the joker 5 give 6 options per card, 6^5 = 7776 entries.
+pub const JOKER_MATRIX_LEN: usize = 6;
+/// `TYP_MATRIX` of part 1 with jokers, for the O(1) lookup instead of sorting on every hand.
+pub const TYP_MATRIX_JOKER: [[[[[Typ; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN] = initialize_typ_matrix_joker();
+//every one of the 6^5 reduced hands, a 5 in any position being a joker
+pub const fn initialize_typ_matrix_joker() -> [[[[[Typ; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN] {
+    let mut typ_matrix = [[[[[Typ::HighCard; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN]; JOKER_MATRIX_LEN];
+
+    let mut i0: usize = 0;
+    while i0 < JOKER_MATRIX_LEN {
+        let mut i1: usize = 0;
+        while i1 < JOKER_MATRIX_LEN {
+            let mut i2: usize = 0;
+            while i2 < JOKER_MATRIX_LEN {
+                let mut i3: usize = 0;
+                while i3 < JOKER_MATRIX_LEN {
+                    let mut i4: usize = 0;
+                    while i4 < JOKER_MATRIX_LEN {
+                        typ_matrix[i0][i1][i2][i3][i4] = identify_joker_hand_type([i0, i1, i2, i3, i4]);
+                        i4 += 1;
+                    }
+                    i3 += 1;
+                }
+                i2 += 1;
+            }
+            i1 += 1;
+        }
+        i0 += 1;
+    }
 
-// T55J5 684
-fn parse_line(line: String) -> (Hand, usize) {
-    let (hand, bid) = match line.split_once(' ') {
-        Some(tuple) => tuple,
-        None => panic!("line should contain exactly one blank space")
-    };
-
-    let cards: [usize; 5] = hand.chars()
-        .map(|c| to_card(c))
-        .collect::<Vec<usize>>()
-        .try_into()
-        .unwrap();
-
-    let cards_reduced_range = reduce_variant_range(cards);
-    //try with dynamic calculation for maintainability
-    //could instead increase the size of the TYP_MATRIX to 6 options ^ 5 cards
-    let typ = identify_hand_type(cards_reduced_range);
-
-    let hand = Hand {
-        typ: typ,
-        cards: cards
-    };
-    let bid = bid.parse::<usize>().expect("Should be a positive integer");
-
-    (hand, bid)
+    typ_matrix
 }
 
-/// Replaced static with half-static variant, as it's performance difference is
-/// expressable in Centimeters of "light travel velocity".
-/// It's around 100 picoseconds ~ 30cm of light
-/// while the method takes ~ 15m
-/// but way more maintainable
-pub fn reduce_variant_range(cards: [usize; 5]) -> [usize; 5] {
-    //init with 8, which is > the max real value of 4
-    let mut variant_map = [8 as usize; 13];
-    let mut result = [0 as usize; 5];
-    variant_map[0] = 5; //joker is 5
-    
-    for i in 0..5 {
-        result[i] = match variant_map[cards[i]] {
-            // 0 => 5, //map the 0 (J) to the joker (5) which is > the max card 4
-            8 => {
-                variant_map[cards[i]] = i;
-                i
-            },
-            _ => variant_map[cards[i]]
-        };
+/// `identify_hand_type` without sorting, so it can fill `TYP_MATRIX_JOKER` at compile time.
+pub const fn identify_joker_hand_type(cards: [usize; 5]) -> Typ {
+    let mut occurrences = [0_usize; JOKER_MATRIX_LEN];
+    let mut i = 0;
+    while i < cards.len() {
+        occurrences[cards[i]] += 1;
+        i += 1;
     }
 
-    return result;
+    let mut largest = 0;
+    let mut second = 0;
+    let mut i = 0;
+    while i < 5 /*skip joker*/ {
+        if occurrences[i] > largest {
+            second = largest;
+            largest = occurrences[i];
+        } else if occurrences[i] > second {
+            second = occurrences[i];
+        }
+        i += 1;
+    }
+    //the jokers join the largest group, splitting them up never makes a better hand
+    match (largest + occurrences[5], second) {
+        (5, _) => Typ::FiveOfAKind,
+        (4, _) => Typ::FourOfAKind,
+        (3, 2) => Typ::FullHouse,
+        (3, _) => Typ::ThreeOfAKind,
+        (2, 2) => Typ::TwoPair,
+        (2, _) => Typ::OnePair,
+        _ => Typ::HighCard,
+    }
 }
 
-//cards can contain a nr'5' meaning Joker.
+//cards are reduced like `RuleSet::reduce_variant_range`, they can contain a nr'5' meaning Joker.
 //joker counts towards all possibilities and evaluates to the best.
 pub fn identify_hand_type(cards: [usize; 5]) -> Typ {
     //card to occurrence count mapping
-    let mut occurrences = [0 as usize; 6]; //up to 4 normal cards + 1 joker at position 5
+    let mut occurrences = [0_usize; 6]; //up to 4 normal cards + 1 joker at position 5
     let mut i = 0;
     while i < cards.len() {
         let card = cards[i];
@@ -164,9 +142,9 @@ pub fn identify_hand_type(cards: [usize; 5]) -> Typ {
                 //two pairs and a joker
                 return Typ::FullHouse;
             } else {
-                //is this even possible? that two comes before three?
-                //does it matter to which pair I add the joker, if two pairs and a joker exist?
-                //11J22 
+                //the occurrences are sorted, so a three always comes before a pair,
+                //and with two pairs the joker goes to the first one: 11J22 is a full house.
+                //every hand is checked against a brute force in `day7_rules`
                 has_three = true;
                 joker_count = 0; //reset joker count, as it's used
             }
@@ -195,23 +173,22 @@ pub fn identify_hand_type(cards: [usize; 5]) -> Typ {
 mod tests {
     use crate::y2023::day7_2::{*};
     use crate::y2023::day7_1::{sort_hands_asc, initialize_typ_matrix};
+    use crate::examples;
     
+    fn parse_line(line: &str) -> Result<(Hand, usize), ParseError> {
+        RuleSet::jokers().parse_line(line)
+    }
+
     #[test]
     fn test_to_card() { 
-        assert!(to_card('J') < to_card('2'));
+        let rules = RuleSet::jokers();
+        assert!(rules.rank('J').unwrap() < rules.rank('2').unwrap());
     }
     #[test]
     fn test_sort_hands_asc() {
-        let input = "32T3K 765\n\
-                     T55J5 684\n\
-                     KK677 28\n\
-                     KTJJT 220\n\
-                     QQQJA 483";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-
+        let input = examples::load(2023, 7, 2).unwrap();
         //new J rule:
-        let mut hands = parse_lines(lines);
+        let mut hands = parse_lines(&input).unwrap();
         assert_eq!(Typ::OnePair, hands[0].0.typ);
         assert_eq!(Typ::FourOfAKind, hands[1].0.typ);
         assert_eq!(Typ::TwoPair, hands[2].0.typ);
@@ -243,11 +220,8 @@ mod tests {
     fn test_hands_of_example() {
         let input = "QQQQ2 1\n\
                      JKKK2 2";
-        let lines = Box::new(input.split("\n")
-            .map(|line| line.to_string()));
-
         //new J rule:
-        let mut hands = parse_lines(lines);
+        let mut hands = parse_lines(input).unwrap();
         assert_eq!(1, hands[0].1); //QQQQ2
         assert_eq!(2, hands[1].1);  //JKKK2
 
@@ -269,6 +243,20 @@ mod tests {
         assert_eq!(Typ::OnePair, typ_matrix[0][0][1][2][3]);
         assert_eq!(Typ::HighCard, typ_matrix[0][1][2][3][4]);
     }
+    #[test]
+    fn test_initialize_typ_matrix_joker() {
+        let typ_matrix = initialize_typ_matrix_joker();
+
+        assert_eq!(Typ::FiveOfAKind, typ_matrix[5][5][5][5][5]);
+        assert_eq!(Typ::FiveOfAKind, typ_matrix[0][0][0][0][5]);
+        assert_eq!(Typ::FourOfAKind, typ_matrix[0][1][5][5][1]);
+        assert_eq!(Typ::FullHouse, typ_matrix[0][0][5][3][3]);
+        assert_eq!(Typ::ThreeOfAKind, typ_matrix[0][1][5][3][1]);
+        assert_eq!(Typ::OnePair, typ_matrix[0][1][2][3][5]);
+        assert_eq!(Typ::TwoPair, TYP_MATRIX_JOKER[0][0][1][1][2]);
+        assert_eq!(Typ::HighCard, TYP_MATRIX_JOKER[0][1][2][3][4]);
+    }
+
     #[test]
     fn test_identify_hand_type() {
         //test with max 5 variants
@@ -284,7 +272,7 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let input = "32T3K 765";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = parse_line(input).unwrap();
 
         assert_eq!(Typ::OnePair, hand.typ);
         //joker is 0, everything below J is shifted up by 1
@@ -295,25 +283,18 @@ mod tests {
     #[test]
     fn test_parse_line_with_joker() {
         let input = "T55J5 684";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = parse_line(input).unwrap();
 
         assert_eq!(Typ::FourOfAKind, hand.typ);
         assert_eq!([9, 4, 4, 0, 4], hand.cards);
         assert_eq!(684, bid);
     }
 
-    #[test]
-    fn test_reduce_variant_range() {
-        // input == output
-        assert_eq!([5, 5, 5, 5, 5], reduce_variant_range([0, 0, 0, 0, 0]));
-        assert_eq!([0, 1, 0, 3, 5], reduce_variant_range([2, 3, 2, 4, 0]));
-    }
-
     #[test]
     fn test_problem_with_full_hourse() {
         // Hand { typ: FullHouse, cards: [0, 2, 9, 2, 12] } 674 - J3T3A 674
         let input = "J3T3A 674";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = parse_line(input).unwrap();
 
         assert_eq!(Typ::ThreeOfAKind, hand.typ);
         assert_eq!([0, 2, 9, 2, 12], hand.cards);
@@ -324,7 +305,7 @@ mod tests {
     fn test_problem_with_full_hourse2() {
         // Hand { typ: FullHouse, cards: [0, 2, 9, 2, 12] } 674 - J3T3A 674
         let input = "KTJJT 674";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = parse_line(input).unwrap();
 
         assert_eq!(Typ::FourOfAKind, hand.typ);
         assert_eq!([11, 9, 0, 0, 9], hand.cards);
@@ -335,7 +316,7 @@ mod tests {
     fn test_problem_with_full_hourse3() {
         // Hand { typ: FullHouse, cards: [0, 1, 6, 6, 11] } 633 - J277K 633
         let input = "J277K 633";
-        let (hand, bid): (Hand, usize) = parse_line(input.to_string());
+        let (hand, bid): (Hand, usize) = parse_line(input).unwrap();
 
         assert_eq!(Typ::ThreeOfAKind, hand.typ);
         assert_eq!([0, 1, 6, 6, 11], hand.cards);
@@ -0,0 +1,255 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::y2023::day7_1::{reduce_variant_range, Typ, TYP_MATRIX};
+
+/// The ranks from weakest to strongest, a card's rank is its index like in part 1.
+pub const RANKS: &str = "23456789TJQKA";
+pub const SUITS: &str = "cdhs";
+
+/// A card of a standard deck, written like `As` or `Td`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: usize,
+    pub suit: usize,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rank = RANKS.as_bytes()[self.rank] as char;
+        let suit = SUITS.as_bytes()[self.suit] as char;
+        write!(f, "{}{}", rank, suit)
+    }
+}
+
+/// Poker's hand categories, ordering is important like for `Typ`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    /// Only with the same card more than once, e.g. from several decks.
+    FiveOfAKind,
+}
+
+/// An evaluated 5 card hand, ordered like in poker: by category, then by `ranks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PokerHand {
+    pub category: Category,
+    /// The ranks in tie break order, not the order they were dealt in: larger groups first,
+    /// stronger ranks first within the same group size, e.g. `KKK22`, `QQ772` or `AT843`.
+    /// Straights start with their top card, the wheel `A2345` is `5432A`.
+    pub ranks: [usize; 5],
+}
+
+//the rank bits of a wheel, A 5 4 3 2
+const WHEEL: usize = 0b1_0000_0000_1111;
+/// The top card of the straight made by a set of ranks, a bit per rank, +1 so 0 is no straight.
+pub const STRAIGHTS: [u8; 1 << 13] = initialize_straights();
+//only the 10 masks of 5 ranks in a row are set, every other set of ranks stays 0
+pub const fn initialize_straights() -> [u8; 1 << 13] {
+    let mut straights = [0_u8; 1 << 13];
+    //the 5 is the top card of the wheel
+    straights[WHEEL] = 3 + 1;
+    let mut top = 4;
+    while top < 13 {
+        straights[0b11111 << (top - 4)] = top as u8 + 1;
+        top += 1;
+    }
+    straights
+}
+
+/// Evaluates exactly 5 cards.
+///
+/// The groups of equal ranks come from part 1's `TYP_MATRIX`, straights from `STRAIGHTS`,
+/// so besides ordering the ranks for the tie break it's lookups only.
+pub fn evaluate(cards: [Card; 5]) -> PokerHand {
+    let ranks = cards.map(|card| card.rank);
+    let reduced = reduce_variant_range(ranks);
+    let typ = TYP_MATRIX[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]];
+
+    let rank_set = ranks.iter().fold(0_usize, |set, rank| set | 1 << rank);
+    let straight = STRAIGHTS[rank_set];
+    let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+
+    let category = match (typ, straight > 0, flush) {
+        (Typ::HighCard, true, true) => Category::StraightFlush,
+        (Typ::HighCard, false, true) => Category::Flush,
+        (Typ::HighCard, true, false) => Category::Straight,
+        (Typ::HighCard, false, false) => Category::HighCard,
+        (Typ::OnePair, _, _) => Category::OnePair,
+        (Typ::TwoPair, _, _) => Category::TwoPair,
+        (Typ::ThreeOfAKind, _, _) => Category::ThreeOfAKind,
+        (Typ::FullHouse, _, _) => Category::FullHouse,
+        (Typ::FourOfAKind, _, _) => Category::FourOfAKind,
+        (Typ::FiveOfAKind, _, _) => Category::FiveOfAKind,
+    };
+
+    let mut counts = [0_usize; 13];
+    for rank in ranks {
+        counts[rank] += 1;
+    }
+    let mut ranks = ranks;
+    ranks.sort_unstable_by(|a, b| counts[*b].cmp(&counts[*a]).then(b.cmp(a)));
+    if straight > 0 && rank_set == WHEEL {
+        //the ace is the lowest card of the wheel
+        ranks.rotate_left(1);
+    }
+    return PokerHand { category: category, ranks: ranks };
+}
+
+/// The best 5 of `cards`, e.g. of the 7 in Texas hold'em. None if there are fewer than 5.
+///
+/// Tries every 5 of them, that's 21 for 7 cards but grows with the fifth power of the cards.
+pub fn best_hand(cards: &[Card]) -> Option<PokerHand> {
+    let n = cards.len();
+    let mut best: Option<PokerHand> = None;
+    //every 5 of the cards, by their indices in ascending order
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        best = best.max(Some(evaluate([cards[a], cards[b], cards[c], cards[d], cards[e]])));
+                    }
+                }
+            }
+        }
+    }
+    return best;
+}
+
+/// Cards separated by spaces, e.g. `As Kd 7h 7c 2s`.
+pub fn parse_cards(line: &str) -> Result<Vec<Card>, ParseError> {
+    let mut cards: Vec<Card> = vec![];
+    for text in line.split_whitespace() {
+        let mut chars = text.chars();
+        let (Some(rank), Some(suit), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(ParseError::at(format!("'{}' should be a rank and a suit, like 'As'", text), line, text));
+        };
+        let card = Card {
+            rank: RANKS.find(rank).ok_or_else(|| ParseError::at(format!("Unhandled rank: {}", rank), line, text))?,
+            suit: SUITS.find(suit).ok_or_else(|| ParseError::at(format!("Unhandled suit: {}", suit), line, &text[1..]))?,
+        };
+        if cards.contains(&card) {
+            return Err(ParseError::at(format!("'{}' is there twice", card), line, text));
+        }
+        cards.push(card);
+    }
+    return Ok(cards);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(line: &str) -> PokerHand {
+        let cards: [Card; 5] = parse_cards(line).unwrap().try_into().unwrap();
+        evaluate(cards)
+    }
+
+    fn ranks(text: &str) -> [usize; 5] {
+        let ranks: Vec<usize> = text.chars().map(|rank| RANKS.find(rank).unwrap()).collect();
+        ranks.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(PokerHand { category: Category::HighCard, ranks: ranks("AT843") }, hand("3c 8d Ah Ts 4s"));
+        assert_eq!(PokerHand { category: Category::OnePair, ranks: ranks("77KQ2") }, hand("7c Kd 7h 2s Qs"));
+        assert_eq!(PokerHand { category: Category::TwoPair, ranks: ranks("QQ774") }, hand("7c Qd 7h 4s Qs"));
+        assert_eq!(PokerHand { category: Category::ThreeOfAKind, ranks: ranks("222A3") }, hand("2c 2d 2h As 3s"));
+        assert_eq!(PokerHand { category: Category::Straight, ranks: ranks("98765") }, hand("5c 6d 7h 8s 9s"));
+        assert_eq!(PokerHand { category: Category::Flush, ranks: ranks("KJ952") }, hand("2h 5h 9h Jh Kh"));
+        assert_eq!(PokerHand { category: Category::FullHouse, ranks: ranks("333KK") }, hand("Kc 3d Kh 3s 3h"));
+        assert_eq!(PokerHand { category: Category::FourOfAKind, ranks: ranks("99994") }, hand("9c 9d 4h 9s 9h"));
+        assert_eq!(PokerHand { category: Category::StraightFlush, ranks: ranks("AKQJT") }, hand("Ts Js Qs Ks As"));
+    }
+
+    #[test]
+    fn test_straights() {
+        assert_eq!(Category::Straight, hand("Ah 2d 3h 4s 5s").category);
+        assert_eq!(ranks("5432A"), hand("Ah 2d 3h 4s 5s").ranks);
+        assert_eq!(Category::StraightFlush, hand("Ad 2d 3d 4d 5d").category);
+        //no wrapping around the ace
+        assert_eq!(Category::HighCard, hand("Qh Kd Ah 2s 3s").category);
+
+        assert!(hand("Ah 2d 3h 4s 5s") < hand("2h 3d 4h 5s 6s"));
+        assert!(hand("Th Jd Qh Ks As") > hand("9h Td Jh Qs Ks"));
+        assert!(hand("Th Jd Qh Ks As") < hand("2h 5h 9h Jh Kh"));
+    }
+
+    #[test]
+    fn test_tie_break_by_rank() {
+        //unlike Camel Cards the position doesn't matter
+        assert_eq!(hand("Kc Kd 2h 2s 3s"), hand("2c 3d Kh 2d Ks"));
+        //the pair first, then the kickers
+        assert!(hand("Qc Qd Ah 5s 3s") < hand("2c Kd Kh 3d 4s"));
+        assert!(hand("Kc Kd Ah 5s 3s") > hand("Kh Ks Qh Js Ts"));
+        //the three of a full house first
+        assert!(hand("3c 3d 3h As Ad") < hand("4c 4d 4h 2s 2d"));
+        //flushes by their highest card that differs
+        assert!(hand("2h 5h 9h Jh Kh") > hand("2d 5d 8d Jd Kd"));
+    }
+
+    #[test]
+    fn test_best_hand() {
+        //the board 3h 7d 8c 9s Tc with either two of each player
+        let alice = best_hand(&parse_cards("Jh 2c 3h 7d 8c 9s Tc").unwrap()).unwrap();
+        let bob = best_hand(&parse_cards("Ts Td 3h 7d 8c 9s Tc").unwrap()).unwrap();
+        assert_eq!(PokerHand { category: Category::Straight, ranks: ranks("JT987") }, alice);
+        assert_eq!(PokerHand { category: Category::ThreeOfAKind, ranks: ranks("TTT98") }, bob);
+        assert!(alice > bob);
+
+        let flush = best_hand(&parse_cards("Ah Kh 2h 7h 9c 9d 4h").unwrap()).unwrap();
+        assert_eq!(PokerHand { category: Category::Flush, ranks: ranks("AK742") }, flush);
+        assert_eq!(None, best_hand(&parse_cards("Ah Kh").unwrap()));
+    }
+
+    #[test]
+    fn test_best_hand_of_many_cards() {
+        //more cards than bits in a u32, the royal flush of spades is among them
+        let cards: Vec<Card> = (0..33).map(|idx| Card { rank: 12 - idx % 13, suit: 3 - idx / 13 }).collect();
+        let best = best_hand(&cards).unwrap();
+        assert_eq!(PokerHand { category: Category::StraightFlush, ranks: ranks("AKQJT") }, best);
+
+        //the spades alone have the same best hand
+        let spades: Vec<Card> = cards.iter().filter(|card| card.suit == 3).copied().collect();
+        assert_eq!(best_hand(&spades), Some(best));
+    }
+
+    #[test]
+    fn test_all_hands_of_a_deck() {
+        let deck: Vec<Card> = (0..52).map(|idx| Card { rank: idx % 13, suit: idx / 13 }).collect();
+        let mut counts = [0_usize; 10];
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            counts[evaluate([deck[a], deck[b], deck[c], deck[d], deck[e]]).category as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        //the well known frequencies of the 2,598,960 hands
+        assert_eq!([1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40, 0], counts);
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        assert_eq!(vec![Card { rank: 12, suit: 3 }, Card { rank: 8, suit: 1 }], parse_cards("As Td").unwrap());
+        assert_eq!("As Td", parse_cards("As Td").unwrap().iter().map(|card| card.to_string()).collect::<Vec<_>>().join(" "));
+        assert_eq!("column 4: Unhandled rank: 1", parse_cards("As 1d").unwrap_err().to_string());
+        assert_eq!("column 5: Unhandled suit: x", parse_cards("As Kx").unwrap_err().to_string());
+        assert_eq!("column 4: 'Asd' should be a rank and a suit, like 'As'", parse_cards("Kh Asd").unwrap_err().to_string());
+        assert_eq!("column 7: 'As' is there twice", parse_cards("As Kd As").unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+
+use crate::error::{self, parse_number, ParseError};
+use crate::y2023::day7_1::{Hand, Typ, TYP_MATRIX};
+use crate::y2023::day7_2::TYP_MATRIX_JOKER;
+
+/// The rules of a Camel Cards variant: which cards there are, which of them are wild
+/// and how hands of the same type are ordered.
+///
+/// Part 1 is `RuleSet::camel_cards()`, part 2 `RuleSet::jokers()`. A `Hand`'s cards are the
+/// ranks within the alphabet of the rule set it was parsed with, so hands are only comparable
+/// under the same rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    alphabet: &'static str,
+    //bit per rank
+    wild: u16,
+    tie_break: TieBreak,
+}
+
+/// A named way to get the type of some cards, see `RuleSet::check_exhaustively`.
+pub type Classifier<'a> = (&'a str, &'a dyn Fn([usize; 5]) -> Typ);
+
+/// How two hands of the same type are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The first card that differs decides, the puzzle's rule.
+    Position,
+    /// The strongest card that differs decides, no matter where in the hand it is.
+    Strongest,
+}
+
+impl RuleSet {
+    /// `alphabet` is the cards from weakest to strongest, at most 16 of them.
+    pub fn new(alphabet: &'static str) -> RuleSet {
+        assert!(alphabet.chars().count() <= 16, "at most 16 cards, but were {}", alphabet);
+        RuleSet { alphabet: alphabet, wild: 0, tie_break: TieBreak::Position }
+    }
+
+    /// Part 1.
+    pub fn camel_cards() -> RuleSet {
+        RuleSet::new("23456789TJQKA")
+    }
+
+    /// Part 2, the J is the weakest card but stands in for whatever makes the best type.
+    pub fn jokers() -> RuleSet {
+        RuleSet::new("J23456789TQKA").with_wild('J')
+    }
+
+    /// `card` stands in for whatever card makes the best type, it still breaks ties as itself.
+    pub fn with_wild(mut self, card: char) -> RuleSet {
+        let rank = self.rank(card).unwrap_or_else(|| panic!("'{}' is not one of the cards {}", card, self.alphabet));
+        self.wild |= 1 << rank;
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> RuleSet {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Index in the alphabet, the higher the stronger.
+    pub fn rank(&self, card: char) -> Option<usize> {
+        self.alphabet.chars().position(|c| c == card)
+    }
+
+    pub fn is_wild(&self, rank: usize) -> bool {
+        self.wild & (1 << rank) != 0
+    }
+
+    /// The hand as the puzzle writes it, e.g. `KTJJT`.
+    pub fn text(&self, cards: [usize; 5]) -> String {
+        cards.iter()
+            .map(|rank| self.alphabet.chars().nth(*rank).unwrap_or('?'))
+            .collect()
+    }
+
+    /// Every hand there is, `alphabet^5` of them.
+    pub fn all_hands(&self) -> impl Iterator<Item = [usize; 5]> {
+        let len = self.alphabet.chars().count();
+        (0..len.pow(5)).map(move |mut idx| {
+            let mut cards = [0_usize; 5];
+            for card in cards.iter_mut().rev() {
+                *card = idx % len;
+                idx /= len;
+            }
+            cards
+        })
+    }
+
+    /// The type by substituting every wild card with every card, slow but obviously right.
+    ///
+    /// All cards that aren't in the hand yet make the same type, so only one of them is tried,
+    /// otherwise the 5 joker hand alone would be 13^5 substitutions.
+    pub fn brute_force_typ(&self, cards: [usize; 5]) -> Typ {
+        fn best(cards: [usize; 5], wild: [bool; 5]) -> Typ {
+            let Some(idx) = wild.iter().position(|is_wild| *is_wild) else {
+                return typ_of_counts(cards);
+            };
+            let mut substituted_wild = wild;
+            substituted_wild[idx] = false;
+            let mut best_typ = Typ::HighCard;
+            //the cards standing in, a card of the hand or a rank no alphabet has
+            for other in 0..=5 {
+                if other < 5 && wild[other] {
+                    continue;
+                }
+                let mut substituted = cards;
+                substituted[idx] = if other < 5 { cards[other] } else { 16 + idx };
+                best_typ = best_typ.max(best(substituted, substituted_wild));
+            }
+            return best_typ;
+        }
+        best(cards, cards.map(|card| self.is_wild(card)))
+    }
+
+    /// Compares the named classifiers to `brute_force_typ` on `all_hands`, returns how many hands
+    /// were checked or the first hand one of them got wrong.
+    pub fn check_exhaustively(&self, classifiers: &[Classifier]) -> Result<usize, String> {
+        let mut checked = 0;
+        for cards in self.all_hands() {
+            let expected = self.brute_force_typ(cards);
+            for (name, classify) in classifiers {
+                let typ = classify(cards);
+                if typ != expected {
+                    return Err(format!("{}: {} is {:?}, but should be {:?}", name, self.text(cards), typ, expected));
+                }
+            }
+            checked += 1;
+        }
+        return Ok(checked);
+    }
+
+    /// Names every card by the position it first shows up at, wild cards become the joker 5
+    /// of `day7_2::TYP_MATRIX_JOKER`.
+    pub fn reduce_variant_range(&self, cards: [usize; 5]) -> [usize; 5] {
+        let mut result = [0_usize; 5];
+        for i in 0..5 {
+            result[i] = match self.is_wild(cards[i]) {
+                true => 5,
+                false => cards[..i].iter().position(|card| *card == cards[i]).unwrap_or(i),
+            };
+        }
+        return result;
+    }
+
+    pub fn typ(&self, cards: [usize; 5]) -> Typ {
+        let reduced = self.reduce_variant_range(cards);
+        if self.wild == 0 {
+            return TYP_MATRIX[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]];
+        }
+        return TYP_MATRIX_JOKER[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]];
+    }
+
+    /// The stronger type wins, the tie break decides between the same types.
+    pub fn cmp(&self, a: &Hand, b: &Hand) -> Ordering {
+        match self.tie_break {
+            TieBreak::Position => a.cmp(b),
+            TieBreak::Strongest => a.typ.cmp(&b.typ).then_with(|| strongest_first(a.cards).cmp(&strongest_first(b.cards))),
+        }
+    }
+
+    pub fn sort_hands_asc(&self, hands: &mut [(Hand, usize)]) {
+        //ignore the bids
+        hands.sort_by(|a, b| self.cmp(&a.0, &b.0));
+    }
+
+    /// The sum of every bid times its hand's rank, the weakest hand has rank 1.
+    pub fn total_winnings(&self, mut hands: Vec<(Hand, usize)>) -> usize {
+        self.sort_hands_asc(&mut hands);
+        hands.into_iter().enumerate()
+            .map(|(idx, (_, bid))| (idx + 1) * bid)
+            .sum()
+    }
+
+    pub fn parse_lines(&self, text: &str) -> Result<Vec<(Hand, usize)>, ParseError> {
+        error::parse_lines(text, |line| self.parse_line(line))
+    }
+
+    // 32T3K 765
+    pub fn parse_line(&self, line: &str) -> Result<(Hand, usize), ParseError> {
+        let (hand, bid) = line.split_once(' ')
+            .ok_or_else(|| ParseError::new("line should contain exactly one blank space"))?;
+
+        let cards: [usize; 5] = hand.chars().enumerate()
+            .map(|(idx, char)| self.rank(char)
+                .ok_or_else(|| ParseError::new(format!("Unhandled Card char: {}", char)).with_column(idx + 1)))
+            .collect::<Result<Vec<usize>, ParseError>>()?
+            .try_into()
+            .map_err(|cards: Vec<usize>| ParseError::new(format!("Hand should have 5 cards, but had {}", cards.len())).with_column(1))?;
+
+        let hand = Hand {
+            typ: self.typ(cards),
+            cards: cards
+        };
+        let bid = parse_number::<usize>(line, bid)?;
+
+        Ok((hand, bid))
+    }
+}
+
+/// Without wild cards only the two largest groups of the same card matter.
+fn typ_of_counts(cards: [usize; 5]) -> Typ {
+    //per card, so a full house is [3, 3, 3, 2, 2] and the second group starts after the first
+    let mut counts = [0_usize; 5];
+    for i in 0..5 {
+        for other in cards {
+            counts[i] += (other == cards[i]) as usize;
+        }
+    }
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match (counts[0], counts.get(counts[0]).copied().unwrap_or(0)) {
+        (5, _) => Typ::FiveOfAKind,
+        (4, _) => Typ::FourOfAKind,
+        (3, 2) => Typ::FullHouse,
+        (3, _) => Typ::ThreeOfAKind,
+        (2, 2) => Typ::TwoPair,
+        (2, _) => Typ::OnePair,
+        _ => Typ::HighCard,
+    }
+}
+
+fn strongest_first(mut cards: [usize; 5]) -> [usize; 5] {
+    cards.sort_unstable_by(|a, b| b.cmp(a));
+    return cards;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2023::{day7_1, day7_1_7bit_matrix, day7_1_matrix_13, day7_1_slow_methods, day7_2};
+
+    fn hand(rules: &RuleSet, cards: &str) -> Hand {
+        rules.parse_line(&format!("{} 1", cards)).unwrap().0
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(Some(9), RuleSet::camel_cards().rank('J'));
+        assert_eq!(Some(0), RuleSet::jokers().rank('J'));
+        assert_eq!(None, RuleSet::jokers().rank('1'));
+        assert!(RuleSet::jokers().is_wild(0));
+        assert!(!RuleSet::camel_cards().is_wild(9));
+    }
+
+    #[test]
+    fn test_reduce_variant_range() {
+        let rules = RuleSet::jokers();
+        assert_eq!([5, 5, 5, 5, 5], rules.reduce_variant_range([0, 0, 0, 0, 0]));
+        assert_eq!([0, 1, 0, 3, 5], rules.reduce_variant_range([2, 3, 2, 4, 0]));
+        //without wild cards the same as part 1's
+        assert_eq!([0, 0, 2, 2, 4], RuleSet::camel_cards().reduce_variant_range([0, 0, 11, 11, 12]));
+    }
+
+    #[test]
+    fn test_typ() {
+        assert_eq!(Typ::TwoPair, hand(&RuleSet::camel_cards(), "KTJJT").typ);
+        assert_eq!(Typ::FourOfAKind, hand(&RuleSet::jokers(), "KTJJT").typ);
+
+        //2s and Js are both wild
+        let rules = RuleSet::camel_cards().with_wild('2').with_wild('J');
+        assert_eq!(Typ::ThreeOfAKind, hand(&rules, "2J345").typ);
+        assert_eq!(Typ::FiveOfAKind, hand(&rules, "2JJ2A").typ);
+        assert_eq!(Typ::FullHouse, hand(&rules, "2KKAA").typ);
+    }
+
+    #[test]
+    fn test_brute_force_typ() {
+        let rules = RuleSet::jokers();
+        assert_eq!(Typ::FourOfAKind, rules.brute_force_typ([9, 4, 4, 0, 4]));
+        assert_eq!(Typ::FiveOfAKind, rules.brute_force_typ([0; 5]));
+        assert_eq!(Typ::OnePair, rules.brute_force_typ([0, 1, 2, 3, 4]));
+        assert_eq!(Typ::FullHouse, rules.brute_force_typ([0, 1, 1, 2, 2]));
+        assert_eq!(Typ::TwoPair, RuleSet::camel_cards().brute_force_typ([0, 1, 1, 2, 2]));
+        assert_eq!(371_293, RuleSet::jokers().all_hands().count());
+        assert_eq!("KTJJT", rules.text([11, 9, 0, 0, 9]));
+    }
+
+    #[test]
+    fn test_all_joker_hands() {
+        let rules = RuleSet::jokers();
+        assert_eq!(Ok(371_293), rules.check_exhaustively(&[
+            ("RuleSet::typ", &|cards| rules.typ(cards)),
+            ("identify_hand_type", &|cards| day7_2::identify_hand_type(rules.reduce_variant_range(cards))),
+            ("identify_joker_hand_type", &|cards| day7_2::identify_joker_hand_type(rules.reduce_variant_range(cards))),
+        ]));
+    }
+
+    #[test]
+    fn test_all_hands_with_several_wild_ranks() {
+        //the joker logic never assumed a single wild rank
+        let rules = RuleSet::camel_cards().with_wild('2').with_wild('J');
+        assert_eq!(Ok(371_293), rules.check_exhaustively(&[
+            ("RuleSet::typ", &|cards| rules.typ(cards)),
+        ]));
+    }
+
+    #[test]
+    fn test_all_hands_without_jokers() {
+        let rules = RuleSet::camel_cards();
+        assert_eq!(Ok(371_293), rules.check_exhaustively(&[
+            ("RuleSet::typ", &|cards| rules.typ(cards)),
+            ("identify_hand_type", &|cards| day7_1::identify_hand_type(day7_1::reduce_variant_range(cards))),
+            ("TYP_MATRIX reduce_variant_range_slow", &|cards| {
+                let reduced = day7_1_slow_methods::reduce_variant_range_slow(cards);
+                day7_1::TYP_MATRIX[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]]
+            }),
+            ("TYP_MATRIX reduce_variant_range_half_static", &|cards| {
+                let reduced = day7_1_slow_methods::reduce_variant_range_half_static(cards);
+                day7_1::TYP_MATRIX[reduced[0]][reduced[1]][reduced[2]][reduced[3]][reduced[4]]
+            }),
+            ("TYP_MATRIX_BIT_SHIFT", &|cards| {
+                day7_1_7bit_matrix::TYP_MATRIX_BIT_SHIFT[day7_1_7bit_matrix::reduce_variant_range_bit_shift(cards)]
+            }),
+            ("TYP_MATRIX 13^5", &|cards| day7_1_matrix_13::TYP_MATRIX[cards[0]][cards[1]][cards[2]][cards[3]][cards[4]]),
+            ("identify_hand_type13", &day7_1_slow_methods::identify_hand_type13),
+        ]));
+    }
+
+    #[test]
+    fn test_cmp() {
+        let rules = RuleSet::jokers();
+        //a joker is the weakest card in a tie
+        assert_eq!(Ordering::Less, rules.cmp(&hand(&rules, "JKKK2"), &hand(&rules, "QQQQ2")));
+
+        let (a, b) = (hand(&rules, "2AKQ3"), hand(&rules, "A2KQ3"));
+        assert_eq!(Ordering::Less, rules.cmp(&a, &b));
+        let rules = rules.with_tie_break(TieBreak::Strongest);
+        assert_eq!(Ordering::Equal, rules.cmp(&a, &b));
+        assert_eq!(Ordering::Greater, rules.cmp(&hand(&rules, "2AKQ4"), &b));
+    }
+
+    #[test]
+    fn test_parse_line_errors() {
+        let rules = RuleSet::jokers();
+        assert_eq!("column 4: Unhandled Card char: X", rules.parse_line("32TXK 765").unwrap_err().to_string());
+        assert_eq!("column 1: Hand should have 5 cards, but had 6", rules.parse_line("32TKJJ 765").unwrap_err().to_string());
+    }
+}
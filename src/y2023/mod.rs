@@ -1,18 +1,42 @@
-// pub mod day1_1;
-// pub mod day1_2;
-// pub mod day2_1;
-// pub mod day2_2;
-// pub mod day3_1;
-// pub mod day3_2;
-// pub mod day4_1;
-// pub mod day4_2;
-// pub mod day5_1;
-// pub mod day5_2;
-// pub mod day5_3;
-// pub mod day6_1;
-// pub mod day6_2;
-// pub mod day7_1_7bit_matrix;
-// pub mod day7_1_matrix_13;
-// pub mod day7_1_slow_methods;
-// pub mod day7_1;
-// pub mod day7_2;
\ No newline at end of file
+use crate::solution;
+
+pub mod day1_1;
+pub mod day1_2;
+pub mod day2_1;
+pub mod day2_2;
+pub mod day3_1;
+pub mod day3_2;
+pub mod day4_1;
+pub mod day4_2;
+pub mod day5_1;
+pub mod day5_2;
+pub mod day5_3;
+pub mod day6_1;
+pub mod day6_2;
+pub mod day6_model;
+pub mod day7_1_7bit_matrix;
+pub mod day7_1_matrix_13;
+pub mod day7_1_slow_methods;
+pub mod day7_1;
+pub mod day7_2;
+pub mod day7_poker;
+pub mod day7_rules;
+
+solution::register!(
+    day1_1,
+    day1_2,
+    day2_1,
+    day2_2,
+    day3_1,
+    day3_2,
+    day4_1,
+    day4_2,
+    day5_1,
+    //day5_3 solves part 2 via ranges, day5_2 is the brute force over every seed
+    day5_3,
+    day5_2,
+    day6_1,
+    day6_2,
+    day7_1,
+    day7_2,
+);
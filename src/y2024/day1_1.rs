@@ -1,28 +1,24 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use crate::error::{parse_lines, parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
 
-#[allow(dead_code)]
-pub fn run() -> std::io::Result<()> {
-    let file = File::open("res/y2024/day1_1.txt")?;
-    let reader = BufReader::new(file);
+pub struct Solver;
 
-    let mut left_numbers = Vec::new();
-    let mut right_numbers = Vec::new();
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2024 }
+    fn day(&self) -> u8 { 1 }
+    fn part(&self) -> u8 { 1 }
 
-    let mut sum = 0;
-    for maybe_line in reader.lines() {
-        let line: String = maybe_line.unwrap();
-
-        let all: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
-        let left = all[0].clone();
-        let right = all[1].clone();
-
-        // println!("{} - {}", left, right);
-        //parse first number
-        left_numbers.push(left.parse::<i32>().expect("Expected positive Integer"));
-        right_numbers.push(right.parse::<i32>().expect("Expected positive Integer"));
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
     }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let (mut left_numbers, mut right_numbers) = parse_location_ids(input)?;
+    measure::parsed();
 
+    let mut sum = 0;
     left_numbers.sort();
     right_numbers.sort();
 
@@ -34,7 +30,29 @@ pub fn run() -> std::io::Result<()> {
         sum += diff;
     }
 
-    println!("{}", sum);
-
-    Ok(())
-}
\ No newline at end of file
+    Ok(Answer::from(sum))
+}
+
+/// The left and right list of location ids, one pair per line.
+pub fn parse_location_ids(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let pairs: Vec<(i32, i32)> = parse_lines(input, |line| {
+        let all: Vec<&str> = line.split_whitespace().collect();
+        if all.len() != 2 {
+            return Err(ParseError::new(format!("Expected two location ids, but found {}", all.len())));
+        }
+        Ok((parse_number(line, all[0])?, parse_number(line, all[1])?))
+    })?;
+    Ok(pairs.into_iter().unzip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location_ids() {
+        assert_eq!(Ok((vec![3, 4], vec![4, 3])), parse_location_ids("3   4\n4   3"));
+        assert_eq!("line 2: Expected two location ids, but found 1", parse_location_ids("3   4\n4").unwrap_err().to_string());
+        assert_eq!("line 1, column 5: 'x' is not a valid number", parse_location_ids("3   x").unwrap_err().to_string());
+    }
+}
@@ -1,27 +1,25 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
 use std::collections::HashMap;
 
-#[allow(dead_code)]
-pub fn run() -> std::io::Result<()> {
-    let file = File::open("res/y2024/day1_1.txt")?;
-    let reader = BufReader::new(file);
+use crate::error::Error;
+use crate::measure;
+use crate::solution::{Answer, Solution};
+use crate::y2024::day1_1::parse_location_ids;
 
-    let mut left_numbers = Vec::new();
-    let mut right_numbers = Vec::new();
+pub struct Solver;
 
-    for maybe_line in reader.lines() {
-        let line: String = maybe_line.unwrap();
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2024 }
+    fn day(&self) -> u8 { 1 }
+    fn part(&self) -> u8 { 2 }
 
-        let all: Vec<String> = line.split_whitespace().map(|s| s.to_string()).collect();
-        let left = all[0].clone();
-        let right = all[1].clone();
-
-        // println!("{} - {}", left, right);
-        //parse first number
-        left_numbers.push(left.parse::<i32>().expect("Expected positive Integer"));
-        right_numbers.push(right.parse::<i32>().expect("Expected positive Integer"));
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
     }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let (left_numbers, right_numbers) = parse_location_ids(input)?;
+    measure::parsed();
 
     let mut count_right_occurrences  = HashMap::new();
     for num in right_numbers {
@@ -33,15 +31,10 @@ pub fn run() -> std::io::Result<()> {
 
     let mut similarity_score = 0;
     for num in left_numbers {
-        let count = match count_right_occurrences.get(&num) {
-            Some(count) => count,
-            None => &0,
-        };
+        let count = count_right_occurrences.get(&num).unwrap_or(&0);
 
         similarity_score += num * count;
     }
 
-    println!("{}", similarity_score);
-
-    Ok(())
-}
\ No newline at end of file
+    Ok(Answer::from(similarity_score))
+}
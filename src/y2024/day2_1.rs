@@ -1,47 +1,72 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-pub fn run() -> std::io::Result<()> {
-    let file = File::open("res/y2024/day2_1.txt")?;
-    let reader = BufReader::new(file);
-
-    let mut safe_reports = 0;
-    // let mut reports = Vec::new();
-    for report in reader.lines() {
-        let levels: Vec<i32> = report.unwrap().split_whitespace()
-            .map(|lvl: &str| lvl.parse::<i32>().unwrap())
-            .collect();
-
-        //safe if:
-        // - The levels are either all increasing or all decreasing.
-        // - Any two adjacent levels differ by at least one and at most three.
-        let mut is_increasing = false;
-        let mut is_safe = true;
-        for i in 1..levels.len() {
-            let previous = levels[i - 1];
-            let current = levels[i];
-            let difference = (previous - current).abs();
-            if difference < 1 || difference > 3 {
-                is_safe = false;
-                break;
-            }
-            
-            if i == 1 {
-                is_increasing = previous < current;
-                continue;
-            }
-
-            if is_increasing != (previous < current) {
-                is_safe = false;
-                break;
-            }
-        }
-        if is_safe {
-            safe_reports += 1;
-        }
+use crate::error::{parse_lines, parse_number, Error, ParseError};
+use crate::measure;
+use crate::solution::{Answer, Solution};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2024 }
+    fn day(&self) -> u8 { 2 }
+    fn part(&self) -> u8 { 1 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let reports = parse_reports(input)?;
+    measure::parsed();
+    let safe_reports = reports.iter()
+        .filter(|levels| is_safe(levels))
+        .count();
+
+    Ok(Answer::from(safe_reports))
+}
+
+/// One report per line, its levels separated by whitespace.
+pub fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse_lines(input, |report| report.split_whitespace()
+        .map(|lvl: &str| parse_number::<i32>(report, lvl))
+        .collect())
+}
+
+/// safe if:
+/// - The levels are either all increasing or all decreasing.
+/// - Any two adjacent levels differ by at least one and at most three.
+pub fn is_safe(levels: &[i32]) -> bool {
+    if levels.len() < 2 {
+        return true;
     }
+    let is_increasing = levels[0] < levels[1];
+    return levels.windows(2)
+        .all(|pair| is_safe_step(pair[0], pair[1], is_increasing));
+}
+
+/// Whether `current` may follow `previous` in a report going in the given direction.
+pub fn is_safe_step(previous: i32, current: i32, is_increasing: bool) -> bool {
+    let difference = if is_increasing { current - previous } else { previous - current };
+    return (1..=3).contains(&difference);
+}
 
-    println!("{}", safe_reports);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
-}
\ No newline at end of file
+    #[test]
+    fn test_is_safe() {
+        assert!(is_safe(&[7, 6, 4, 2, 1]));
+        assert!(is_safe(&[1, 3, 6, 7, 9]));
+        assert!(!is_safe(&[1, 2, 7, 8, 9])); //increase of 5
+        assert!(!is_safe(&[1, 3, 2, 4, 5])); //changes direction
+        assert!(!is_safe(&[8, 6, 4, 4, 1])); //no change
+        assert!(is_safe(&[4]));
+        assert!(is_safe(&[]));
+    }
+
+    #[test]
+    fn test_parse_reports() {
+        assert_eq!(Ok(vec![vec![7, 6, 4], vec![1, 2]]), parse_reports("7 6 4\n1 2"));
+        assert_eq!("line 2, column 3: 'x' is not a valid number", parse_reports("7 6 4\n1 x").unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::error::Error;
+use crate::measure;
+use crate::solution::{Answer, Solution};
+use crate::y2024::day2_1::{is_safe_step, parse_reports};
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn year(&self) -> u16 { 2024 }
+    fn day(&self) -> u8 { 2 }
+    fn part(&self) -> u8 { 2 }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        solve(input)
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Error> {
+    let reports = parse_reports(input)?;
+    measure::parsed();
+    let safe_reports = reports.iter()
+        .filter(|levels| is_safe_with_dampener(levels))
+        .count();
+
+    Ok(Answer::from(safe_reports))
+}
+
+/// Safe like in part 1, after removing at most one level.
+///
+/// Instead of re-checking the report without each of its n levels, find the first
+/// unsafe step per direction: one of its two levels has to go, so only those two
+/// removals are checked, which keeps it O(n).
+pub fn is_safe_with_dampener(levels: &[i32]) -> bool {
+    [true, false].into_iter().any(|is_increasing| {
+        match find_unsafe_step(levels, is_increasing, None) {
+            None => true,
+            Some((previous, current)) => find_unsafe_step(levels, is_increasing, Some(previous)).is_none()
+                || find_unsafe_step(levels, is_increasing, Some(current)).is_none(),
+        }
+    })
+}
+
+/// Indices of the first two adjacent levels breaking the rules, ignoring the level at `skip`.
+fn find_unsafe_step(levels: &[i32], is_increasing: bool, skip: Option<usize>) -> Option<(usize, usize)> {
+    let mut remaining = (0..levels.len()).filter(|&idx| Some(idx) != skip);
+    let mut previous = remaining.next()?;
+    for current in remaining {
+        if !is_safe_step(levels[previous], levels[current], is_increasing) {
+            return Some((previous, current));
+        }
+        previous = current;
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2024::day2_1::is_safe;
+
+    #[test]
+    fn test_is_safe_with_dampener() {
+        assert!(is_safe_with_dampener(&[7, 6, 4, 2, 1]));
+        assert!(!is_safe_with_dampener(&[1, 2, 7, 8, 9]));
+        assert!(!is_safe_with_dampener(&[9, 7, 6, 2, 1]));
+        assert!(is_safe_with_dampener(&[1, 3, 2, 4, 5])); //remove 3
+        assert!(is_safe_with_dampener(&[8, 6, 4, 4, 1])); //remove a 4
+        assert!(is_safe_with_dampener(&[1, 3, 6, 7, 9]));
+        //the first level decides the wrong direction
+        assert!(is_safe_with_dampener(&[5, 1, 2, 3, 4]));
+        //the last level is the bad one
+        assert!(is_safe_with_dampener(&[1, 2, 3, 4, 9]));
+        assert!(is_safe_with_dampener(&[3, 3]));
+        assert!(is_safe_with_dampener(&[]));
+    }
+
+    #[test]
+    fn test_matches_removing_every_level() {
+        //compare with the naive n re-checks on every report of 5 levels from 1..=6
+        let mut levels = [1; 5];
+        loop {
+            let naive = (0..levels.len()).any(|skip| {
+                let removed: Vec<i32> = levels.iter().enumerate()
+                    .filter(|&(idx, _)| idx != skip)
+                    .map(|(_, &level)| level)
+                    .collect();
+                is_safe(&removed)
+            });
+            assert_eq!(naive, is_safe_with_dampener(&levels), "{:?}", levels);
+
+            let Some(idx) = levels.iter().position(|&level| level < 6) else { break };
+            levels[idx] += 1;
+            levels[..idx].fill(1);
+        }
+    }
+}
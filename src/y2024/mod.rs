@@ -1,4 +1,13 @@
+use crate::solution;
+
 pub mod day1_1;
 pub mod day1_2;
 pub mod day2_1;
-pub mod day2_2;
\ No newline at end of file
+pub mod day2_2;
+
+solution::register!(
+    day1_1,
+    day1_2,
+    day2_1,
+    day2_2,
+);